@@ -0,0 +1,73 @@
+// full-frame clears at 1080p: the packed u32 kernel vs the old 4 byte chunk copies.
+// run with `cargo bench --bench clear`. no bench framework, just the best of a few
+// timed batches so one noisy batch can't skew it.
+//
+// an 8 MB frame doesn't fit in cache, so with optimizations both kernels end up at
+// memory bandwidth (llvm vectorizes the chunk copies too) and come out within a few
+// percent of each other. this is a regression check, not a speedup.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use codimate::{
+    color::Color,
+    renderer::{frame::Frame, render::Renderer},
+};
+
+const WIDTH: u32 = 1920;
+const HEIGHT: u32 = 1080;
+const BATCHES: usize = 10;
+const CLEARS_PER_BATCH: u32 = 50;
+
+// what `Renderer::clear` did before frames stored words
+fn clear_bytes(fb: &mut Frame, color: Color) {
+    let packed = color.into_rgba();
+    for chunk in fb.as_bytes_mut().chunks_exact_mut(4) {
+        chunk.copy_from_slice(&packed);
+    }
+}
+
+// best time per clear over all batches
+fn time(mut clear: impl FnMut(&mut Frame, Color)) -> Duration {
+    let mut fb = Frame::new(WIDTH, HEIGHT);
+    let colors = [Color::new(30, 144, 255, 255), Color::new(255, 99, 71, 128)];
+
+    // warm up so the frame is paged in before timing
+    clear(&mut fb, colors[0]);
+
+    (0..BATCHES)
+        .map(|_| {
+            let start = Instant::now();
+            for i in 0..CLEARS_PER_BATCH {
+                clear(black_box(&mut fb), black_box(colors[i as usize % 2]));
+            }
+            black_box(fb.as_slice());
+            start.elapsed() / CLEARS_PER_BATCH
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, per_clear: Duration) {
+    let bytes = (WIDTH * HEIGHT * 4) as f64;
+    let gib_per_s = bytes / per_clear.as_secs_f64() / (1u64 << 30) as f64;
+    println!("{name:<12} {per_clear:>12.2?} per clear  {gib_per_s:>7.2} GiB/s");
+}
+
+fn main() {
+    println!("full-frame clear, {WIDTH}x{HEIGHT} rgba8888");
+
+    let bytes = time(clear_bytes);
+    report("4 x u8", bytes);
+
+    let mut renderer = Renderer::new(Vec::new());
+    let words = time(|fb, color| renderer.clear(fb, color));
+    report("u32 words", words);
+
+    println!(
+        "speedup      {:>12.2}x",
+        bytes.as_secs_f64() / words.as_secs_f64()
+    );
+}
@@ -0,0 +1,316 @@
+"""Manim-style scenes on top of the codimate renderer.
+
+Subclass `Scene`, override `construct()`, and build the video out of
+`self.add(...)`, `self.play(...)` and `self.wait(...)` calls:
+
+    class Hello(Scene):
+        def construct(self):
+            box = Rectangle(100, 100, 200, 120, Color.parse("#58c4dd"))
+            self.play(FadeIn(box))
+            self.play(MoveTo(box, 600, 300), run_time=2.0)
+            self.wait()
+
+    frames = Hello(width=1920, height=1080, fps=60).render()
+"""
+
+from __future__ import annotations
+
+import math
+from typing import Callable, Optional
+
+from codimate import Color, Frame, Renderer
+
+RateFunc = Callable[[float], float]
+FrameSink = Callable[[Frame], None]
+
+
+# --- rate functions --- #
+
+
+def linear(t: float) -> float:
+    return t
+
+
+def smooth(t: float) -> float:
+    """Ease in and out along a sigmoid (the manim default)."""
+    # sigmoid remapped so smooth(0) == 0 and smooth(1) == 1
+    inflection = 10.0
+
+    def sigmoid(x: float) -> float:
+        return 1.0 / (1.0 + math.exp(-x))
+
+    error = sigmoid(-inflection / 2.0)
+    value = (sigmoid(inflection * (t - 0.5)) - error) / (1.0 - 2.0 * error)
+    return min(max(value, 0.0), 1.0)
+
+
+def there_and_back(t: float) -> float:
+    return smooth(2.0 * t if t < 0.5 else 2.0 * (1.0 - t))
+
+
+# --- mobjects --- #
+
+
+class Mobject:
+    """Anything that can draw itself into a frame."""
+
+    def __init__(self, color: Color = Color.WHITE) -> None:
+        self.color = color
+
+    def draw(self, renderer: Renderer, frame: Frame) -> None:
+        raise NotImplementedError
+
+
+class Rectangle(Mobject):
+    """An axis-aligned solid rectangle. (x, y) is the top-left corner in pixels."""
+
+    def __init__(
+        self,
+        x: int,
+        y: int,
+        width: int,
+        height: int,
+        color: Color = Color.WHITE,
+    ) -> None:
+        super().__init__(color)
+        self.x = x
+        self.y = y
+        self.width = width
+        self.height = height
+
+    def draw(self, renderer: Renderer, frame: Frame) -> None:
+        # the renderer works in u32, so anything off the top/left edge is trimmed here
+        x0, y0 = max(round(self.x), 0), max(round(self.y), 0)
+        x1 = round(self.x + self.width)
+        y1 = round(self.y + self.height)
+        if x1 <= x0 or y1 <= y0:
+            return
+        renderer.rect(frame, x0, y0, x1 - x0, y1 - y0, self.color)
+
+
+# --- animations --- #
+
+
+class Animation:
+    """Interpolates one mobject over `run_time` seconds.
+
+    Subclasses override `begin()` to capture the starting state and
+    `interpolate(alpha)` to apply the state at `alpha` in [0, 1].
+    """
+
+    def __init__(
+        self,
+        mobject: Mobject,
+        run_time: float = 1.0,
+        rate_func: RateFunc = smooth,
+    ) -> None:
+        self.mobject = mobject
+        self.run_time = run_time
+        self.rate_func = rate_func
+
+    def begin(self, scene: Scene) -> None:
+        pass
+
+    def interpolate(self, alpha: float) -> None:
+        raise NotImplementedError
+
+    def finish(self, scene: Scene) -> None:
+        self.interpolate(1.0)
+
+
+class MoveTo(Animation):
+    """Slide a rectangle so its top-left corner ends up at (x, y)."""
+
+    def __init__(self, mobject: Rectangle, x: float, y: float, **kwargs) -> None:
+        super().__init__(mobject, **kwargs)
+        self.target = (x, y)
+
+    def begin(self, scene: Scene) -> None:
+        self.start = (self.mobject.x, self.mobject.y)
+
+    def interpolate(self, alpha: float) -> None:
+        (x0, y0), (x1, y1) = self.start, self.target
+        self.mobject.x = x0 + (x1 - x0) * alpha
+        self.mobject.y = y0 + (y1 - y0) * alpha
+
+
+class ColorTo(Animation):
+    """Shift a mobject's color in OKLCH space."""
+
+    def __init__(self, mobject: Mobject, color: Color, **kwargs) -> None:
+        super().__init__(mobject, **kwargs)
+        self.target = color
+
+    def begin(self, scene: Scene) -> None:
+        self.start = self.mobject.color
+
+    def interpolate(self, alpha: float) -> None:
+        self.mobject.color = self.start.lerp_oklch(self.target, alpha)
+
+
+class FadeIn(ColorTo):
+    """Fade a mobject in from the scene background. Adds it to the scene if needed."""
+
+    def __init__(self, mobject: Mobject, **kwargs) -> None:
+        super().__init__(mobject, mobject.color, **kwargs)
+
+    def begin(self, scene: Scene) -> None:
+        self.start = scene.background
+        self.mobject.color = scene.background
+        scene.add(self.mobject)
+
+    def interpolate(self, alpha: float) -> None:
+        self.mobject.color = self.start.lerp_linear(self.target, alpha)
+
+
+class FadeOut(ColorTo):
+    """Fade a mobject out into the scene background, then remove it."""
+
+    def __init__(self, mobject: Mobject, **kwargs) -> None:
+        super().__init__(mobject, mobject.color, **kwargs)
+
+    def begin(self, scene: Scene) -> None:
+        self.start = self.mobject.color
+        self.target = scene.background
+
+    def interpolate(self, alpha: float) -> None:
+        self.mobject.color = self.start.lerp_linear(self.target, alpha)
+
+    def finish(self, scene: Scene) -> None:
+        super().finish(scene)
+        scene.remove(self.mobject)
+        # restore the color so the mobject can be re-added later
+        self.mobject.color = self.start
+
+
+# --- scene --- #
+
+
+class Scene:
+    """Base class for codimate scenes.
+
+    Every `play`/`wait` call renders `round(duration * fps)` frames. Frames are
+    handed to `sink` as they're produced, or collected and returned by
+    `render()` when no sink is given.
+    """
+
+    def __init__(
+        self,
+        width: int = 1920,
+        height: int = 1080,
+        fps: int = 30,
+        background: Color = Color.BLACK,
+    ) -> None:
+        if width <= 0 or height <= 0:
+            raise ValueError("scene resolution must be positive")
+        if fps <= 0:
+            raise ValueError("fps must be positive")
+
+        self.width = width
+        self.height = height
+        self.fps = fps
+        self.background = background
+        self.mobjects: list[Mobject] = []
+        self.renderer = Renderer()
+        self.time = 0.0
+        self.frame_count = 0
+        self._sink: Optional[FrameSink] = None
+
+    def construct(self) -> None:
+        """Build the scene. Subclasses override this."""
+
+    def render(self, sink: Optional[FrameSink] = None) -> list[Frame]:
+        """Run `construct()` and produce the frame sequence.
+
+        If `sink` is given each frame is passed to it (e.g. an encoder pipe) and
+        an empty list is returned. Otherwise every frame is kept and returned.
+        """
+        frames: list[Frame] = []
+        self._sink = sink if sink is not None else frames.append
+        try:
+            self.construct()
+        finally:
+            self._sink = None
+        return frames
+
+    def add(self, *mobjects: Mobject) -> None:
+        """Add mobjects to the scene. Later mobjects draw on top."""
+        for mobject in mobjects:
+            if mobject not in self.mobjects:
+                self.mobjects.append(mobject)
+
+    def remove(self, *mobjects: Mobject) -> None:
+        for mobject in mobjects:
+            if mobject in self.mobjects:
+                self.mobjects.remove(mobject)
+
+    def play(self, *animations: Animation, run_time: Optional[float] = None) -> None:
+        """Play animations in parallel.
+
+        `run_time` overrides every animation's own run time; otherwise the
+        longest animation decides how long this call lasts.
+        """
+        if not animations:
+            raise ValueError("play() needs at least one animation")
+        if run_time is not None:
+            for animation in animations:
+                animation.run_time = run_time
+
+        for animation in animations:
+            animation.begin(self)
+            self.add(animation.mobject)
+
+        duration = max(animation.run_time for animation in animations)
+        n = self._frames_for(duration)
+        for i in range(1, n + 1):
+            # spread the frames over the whole duration so the last one lands on the end
+            # state, even when the duration isn't a whole number of frames
+            t = duration * i / n
+            for animation in animations:
+                progress = 1.0 if animation.run_time <= 0 else min(t / animation.run_time, 1.0)
+                animation.interpolate(animation.rate_func(progress))
+            self._emit_frame()
+
+        for animation in animations:
+            animation.finish(self)
+
+    def wait(self, duration: float = 1.0) -> None:
+        """Hold the current state for `duration` seconds."""
+        for _ in range(self._frames_for(duration)):
+            self._emit_frame()
+
+    # --- internals --- #
+
+    def _frames_for(self, duration: float) -> int:
+        if duration < 0:
+            raise ValueError("duration can't be negative")
+        return round(duration * self.fps)
+
+    def _draw(self) -> Frame:
+        frame = Frame(self.width, self.height)
+        self.renderer.clear(frame, self.background)
+        for mobject in self.mobjects:
+            mobject.draw(self.renderer, frame)
+        return frame
+
+    def _emit_frame(self) -> None:
+        if self._sink is None:
+            raise RuntimeError("play()/wait() can only be called while the scene renders")
+        self._sink(self._draw())
+        self.frame_count += 1
+        self.time = self.frame_count / self.fps
+
+
+__all__ = [
+    "Animation",
+    "ColorTo",
+    "FadeIn",
+    "FadeOut",
+    "Mobject",
+    "MoveTo",
+    "Rectangle",
+    "Scene",
+    "linear",
+    "smooth",
+    "there_and_back",
+]
@@ -0,0 +1,209 @@
+"""Tests for the Python bindings. Build the extension first (e.g. `maturin develop`), then run
+`python -m unittest discover python`."""
+
+import os
+import tempfile
+import unittest
+
+from codimate import (
+    Color,
+    ColorVisionDeficiency,
+    Frame,
+    FrameError,
+    PixelFormat,
+    Renderer,
+    YuvRange,
+)
+
+
+class NamedColorTest(unittest.TestCase):
+    def test_parse_accepts_names_in_any_case(self) -> None:
+        self.assertEqual(Color.parse("RebeccaPurple").rgba(), (102, 51, 153, 255))
+        self.assertEqual(Color.parse("transparent").rgba(), (0, 0, 0, 0))
+
+    def test_css_name(self) -> None:
+        self.assertEqual(Color(0, 255, 255).css_name(), "aqua")
+        self.assertEqual(Color(128, 128, 128).css_name(), "gray")
+        self.assertIsNone(Color(1, 2, 3).css_name())
+
+
+class CssSerializerTest(unittest.TestCase):
+    def test_serialized_strings_parse_back(self) -> None:
+        c = Color(255, 136, 0, 128)
+        for text in (c.to_css_rgb(), c.to_css_hsl(), c.to_css_oklch()):
+            self.assertEqual(Color.parse(text), c, text)
+
+    def test_rgb(self) -> None:
+        self.assertEqual(Color(255, 136, 0).to_css_rgb(), "rgb(255 136 0)")
+        self.assertEqual(Color(255, 136, 0, 100).to_css_rgb(precision=2), "rgb(255 136 0 / 0.39)")
+
+
+class ContrastTest(unittest.TestCase):
+    def test_apca_contrast(self) -> None:
+        self.assertAlmostEqual(Color.BLACK.apca_contrast(Color.WHITE), 106.04, places=1)
+        self.assertAlmostEqual(Color.WHITE.apca_contrast(Color.BLACK), -107.88, places=1)
+
+    def test_best_text_color(self) -> None:
+        navy = Color(0, 0, 128)
+        self.assertEqual(Color.best_text_color(navy, [Color.BLACK, Color.WHITE]), Color.WHITE)
+        self.assertIsNone(Color.best_text_color(navy, []))
+
+    def test_adjust_reaches_the_target(self) -> None:
+        gray = Color(150, 150, 150)
+        wcag = gray.adjust_for_contrast(Color.WHITE, 4.5)
+        self.assertGreaterEqual(wcag.contrast_ratio(Color.WHITE), 4.5)
+        apca = gray.adjust_for_apca(Color.WHITE, 75.0)
+        self.assertGreaterEqual(abs(apca.apca_contrast(Color.WHITE)), 75.0)
+
+
+class CvdTest(unittest.TestCase):
+    def test_achromatopsia_is_gray(self) -> None:
+        r, g, b, a = Color(255, 0, 0).simulate_cvd(ColorVisionDeficiency.ACHROMATOPSIA).rgba()
+        self.assertEqual((r, r, a), (g, b, 255))
+
+    def test_zero_severity_is_the_identity(self) -> None:
+        c = Color(255, 136, 0)
+        self.assertEqual(c.simulate_cvd(ColorVisionDeficiency.PROTANOPIA, 0.0), c)
+
+    def test_frame_matches_color(self) -> None:
+        c = Color(40, 200, 90)
+        frame = Frame(2, 2)
+        Renderer().clear(frame, c)
+        frame.simulate_cvd(ColorVisionDeficiency.DEUTERANOPIA)
+        self.assertEqual(frame.get_pixel(1, 1), c.simulate_cvd(ColorVisionDeficiency.DEUTERANOPIA))
+
+
+class DifferenceTest(unittest.TestCase):
+    def test_identical_colors_have_no_difference(self) -> None:
+        c = Color(255, 136, 0)
+        self.assertEqual(c.delta_e_2000(c), 0.0)
+        self.assertEqual(c.delta_e_ok(c), 0.0)
+
+    def test_black_and_white(self) -> None:
+        self.assertAlmostEqual(Color.BLACK.delta_e_2000(Color.WHITE), 100.0, places=1)
+        self.assertAlmostEqual(Color.BLACK.delta_e_ok(Color.WHITE), 1.0, places=3)
+
+    def test_near_colors_are_closer_than_far_ones(self) -> None:
+        c = Color(200, 100, 50)
+        self.assertLess(c.delta_e_2000(Color(201, 100, 50)), c.delta_e_2000(Color(50, 100, 200)))
+
+
+class TemperatureTest(unittest.TestCase):
+    def test_warm_is_redder_than_cool(self) -> None:
+        warm_r, _, warm_b, _ = Color.from_kelvin(2700).rgba()
+        cool_r, _, cool_b, _ = Color.from_kelvin(10000).rgba()
+        self.assertGreater(warm_r / max(warm_b, 1), cool_r / cool_b)
+
+    def test_white_balance_to_the_same_temperature_is_the_identity(self) -> None:
+        c = Color(200, 100, 50)
+        self.assertEqual(c.white_balance(5000, 5000), c)
+
+    def test_frame_matches_color(self) -> None:
+        c = Color(200, 100, 50)
+        frame = Frame(2, 2)
+        Renderer().clear(frame, c)
+        frame.white_balance(3000, 6500)
+        self.assertEqual(frame.get_pixel(0, 1), c.white_balance(3000, 6500))
+
+
+class HarmonyTest(unittest.TestCase):
+    def test_schemes_start_with_the_color(self) -> None:
+        c = Color(200, 100, 50, 128)
+        self.assertEqual(len(c.complementary()), 2)
+        self.assertEqual(len(c.analogous()), 3)
+        self.assertEqual(len(c.triadic()), 3)
+        self.assertEqual(len(c.tetradic()), 4)
+        self.assertEqual(len(c.split_complementary(20)), 3)
+        for scheme in (c.complementary(), c.triadic(), c.tetradic()):
+            self.assertEqual(scheme[0], c)
+            self.assertTrue(all(other.rgba()[3] == 128 for other in scheme))
+
+    def test_rotating_a_gray_keeps_it(self) -> None:
+        gray = Color(128, 128, 128)
+        self.assertEqual(gray.rotate_hue(90), gray)
+
+    def test_tonal_scale_goes_from_light_to_dark(self) -> None:
+        shades = Color(0, 128, 128).tonal_scale(5)
+        self.assertEqual(len(shades), 5)
+        sums = [sum(shade.rgba()[:3]) for shade in shades]
+        self.assertEqual(sums, sorted(sums, reverse=True))
+        self.assertEqual(Color(0, 128, 128).tonal_scale(0), [])
+
+
+class FrameTest(unittest.TestCase):
+    def test_largest_frame_side(self) -> None:
+        frame = Frame(32768, 1)
+        self.assertEqual((frame.width, frame.height), (32768, 1))
+        Renderer().set_pixel(frame, 32767, 0, Color.WHITE)
+        self.assertEqual(frame.get_pixel(32767, 0), Color.WHITE)
+        self.assertIsNone(frame.get_pixel(32768, 0))
+
+    def test_oversized_frame_raises(self) -> None:
+        with self.assertRaises(FrameError):
+            Frame(32769, 1)
+        with self.assertRaises(ValueError):
+            Frame(0xFFFF_FFFF, 0xFFFF_FFFF)
+
+
+class PixelFormatTest(unittest.TestCase):
+    def setUp(self) -> None:
+        self.frame = Frame(4, 2)
+        Renderer().clear(self.frame, Color(255, 136, 0))
+
+    def test_rgb24_and_bgra_byte_order(self) -> None:
+        self.assertEqual(self.frame.to_bytes(PixelFormat.RGB24)[:3], bytes([255, 136, 0]))
+        self.assertEqual(self.frame.to_bytes(PixelFormat.BGRA8888)[:4], bytes([0, 136, 255, 255]))
+
+    def test_round_trip(self) -> None:
+        for format in (PixelFormat.RGBA8888, PixelFormat.RGB24, PixelFormat.BGRA8888):
+            data = self.frame.to_bytes(format)
+            back = Frame.from_bytes(4, 2, data, format)
+            self.assertEqual(back.to_bytes(), self.frame.to_bytes(), format)
+
+    def test_yuv420p(self) -> None:
+        for yuv_range in (YuvRange.LIMITED, YuvRange.FULL):
+            data = self.frame.to_bytes(PixelFormat.YUV420P, yuv_range)
+            self.assertEqual(len(data), 4 * 2 + 2 * (2 * 1))
+            back = Frame.from_bytes(4, 2, data, PixelFormat.YUV420P, yuv_range)
+            r, g, b, a = back.get_pixel(3, 1).rgba()
+            self.assertLessEqual(max(abs(r - 255), abs(g - 136), abs(b - 0)), 2)
+            self.assertEqual(a, 255)
+
+    def test_wrong_length_raises(self) -> None:
+        with self.assertRaises(FrameError):
+            Frame.from_bytes(4, 2, bytes(5), PixelFormat.RGB24)
+
+
+class ImageIoTest(unittest.TestCase):
+    def setUp(self) -> None:
+        self.frame = Frame(3, 2)
+        renderer = Renderer()
+        renderer.clear(self.frame, Color(10, 20, 30, 128))
+        renderer.set_pixel(self.frame, 2, 1, Color(255, 136, 0))
+
+    def test_png_and_qoi_round_trip(self) -> None:
+        for data in (self.frame.encode_png(), self.frame.encode_qoi()):
+            self.assertEqual(Frame.decode_image(data).to_bytes(), self.frame.to_bytes())
+        self.assertEqual(self.frame._repr_png_(), self.frame.encode_png())
+
+    def test_ppm_drops_alpha(self) -> None:
+        back = Frame.decode_image(self.frame.encode_ppm())
+        self.assertEqual(back.get_pixel(0, 0), Color(10, 20, 30))
+        self.assertEqual(back.get_pixel(2, 1), Color(255, 136, 0))
+
+    def test_save_and_load(self) -> None:
+        with tempfile.TemporaryDirectory() as tmp:
+            for ext, save in (("png", self.frame.save_png), ("qoi", self.frame.save_qoi)):
+                path = os.path.join(tmp, f"frame.{ext}")
+                save(path)
+                self.assertEqual(Frame.from_image(path).to_bytes(), self.frame.to_bytes())
+
+    def test_errors(self) -> None:
+        with self.assertRaises(FileNotFoundError):
+            Frame.from_image("/nonexistent/frame.png")
+        with self.assertRaises(ValueError):
+            Frame.decode_image(b"not an image")
+
+
+if __name__ == "__main__":
+    unittest.main()
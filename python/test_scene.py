@@ -0,0 +1,54 @@
+"""Tests for scene.py. Build the extension first (e.g. `maturin develop`), then run
+`python -m unittest discover python`."""
+
+import unittest
+
+from codimate import Color
+
+from scene import FadeIn, MoveTo, Rectangle, Scene, linear
+
+RED = Color(255, 0, 0, 255)
+
+
+class Slide(Scene):
+    """Moves a 4x4 box to (40, 20) over a run time that isn't a whole number of frames."""
+
+    def construct(self) -> None:
+        self.box = Rectangle(0, 0, 4, 4, RED)
+        self.add(self.box)
+        self.play(MoveTo(self.box, 40, 20, rate_func=linear), run_time=0.51)
+
+
+class Fade(Scene):
+    def construct(self) -> None:
+        self.play(FadeIn(Rectangle(0, 0, 8, 8, RED), run_time=0.51))
+
+
+class PlayTest(unittest.TestCase):
+    def test_move_to_reaches_its_target_on_the_last_frame(self) -> None:
+        scene = Slide(width=64, height=48, fps=30)
+        frames = scene.render()
+
+        self.assertEqual(len(frames), round(0.51 * 30))
+        self.assertEqual((scene.box.x, scene.box.y), (40, 20))
+        last = frames[-1]
+        self.assertEqual(last.get_pixel(40, 20), RED)
+        self.assertEqual(last.get_pixel(43, 23), RED)
+        self.assertEqual(last.get_pixel(39, 20), Color.BLACK)
+        self.assertEqual(last.get_pixel(44, 23), Color.BLACK)
+
+    def test_move_to_keeps_moving_until_the_end(self) -> None:
+        frames = Slide(width=64, height=48, fps=30).render()
+        # the box's left edge on each frame
+        xs = [next(x for x in range(64) if f.get_pixel(x, 20) == RED) for f in frames[-3:]]
+        self.assertEqual(xs[-1], 40)
+        self.assertLess(xs[0], xs[1])
+
+    def test_fade_in_ends_fully_opaque(self) -> None:
+        frames = Fade(width=16, height=16, fps=30).render()
+        self.assertEqual(frames[-1].get_pixel(4, 4), RED)
+        self.assertNotEqual(frames[0].get_pixel(4, 4), RED)
+
+
+if __name__ == "__main__":
+    unittest.main()
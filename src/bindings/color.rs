@@ -0,0 +1,428 @@
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::color::{
+    ColorFloat,
+    cvd::ColorVisionDeficiency,
+    model::{BlendMode, Color, CompositeOp},
+    parse::{ColorParseError, parse_color},
+};
+
+pyo3::create_exception!(
+    codimate,
+    PyColorParseError,
+    PyValueError,
+    "Raised when a color string can't be parsed."
+);
+
+impl From<ColorParseError> for PyErr {
+    fn from(err: ColorParseError) -> Self {
+        PyColorParseError::new_err(err.to_string())
+    }
+}
+
+/// Python-facing mirror of `BlendMode`.
+#[pyclass(
+    name = "BlendMode",
+    module = "codimate",
+    eq,
+    eq_int,
+    frozen,
+    from_py_object
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PyBlendMode {
+    #[pyo3(name = "NORMAL")]
+    Normal,
+    #[pyo3(name = "MULTIPLY")]
+    Multiply,
+    #[pyo3(name = "SCREEN")]
+    Screen,
+    #[pyo3(name = "OVERLAY")]
+    Overlay,
+    #[pyo3(name = "DARKEN")]
+    Darken,
+    #[pyo3(name = "LIGHTEN")]
+    Lighten,
+    #[pyo3(name = "COLOR_DODGE")]
+    ColorDodge,
+    #[pyo3(name = "COLOR_BURN")]
+    ColorBurn,
+    #[pyo3(name = "HARD_LIGHT")]
+    HardLight,
+    #[pyo3(name = "SOFT_LIGHT")]
+    SoftLight,
+    #[pyo3(name = "DIFFERENCE")]
+    Difference,
+    #[pyo3(name = "EXCLUSION")]
+    Exclusion,
+    #[pyo3(name = "HUE")]
+    Hue,
+    #[pyo3(name = "SATURATION")]
+    Saturation,
+    #[pyo3(name = "COLOR")]
+    Color,
+    #[pyo3(name = "LUMINOSITY")]
+    Luminosity,
+}
+
+impl From<PyBlendMode> for BlendMode {
+    fn from(mode: PyBlendMode) -> Self {
+        match mode {
+            PyBlendMode::Normal => BlendMode::Normal,
+            PyBlendMode::Multiply => BlendMode::Multiply,
+            PyBlendMode::Screen => BlendMode::Screen,
+            PyBlendMode::Overlay => BlendMode::Overlay,
+            PyBlendMode::Darken => BlendMode::Darken,
+            PyBlendMode::Lighten => BlendMode::Lighten,
+            PyBlendMode::ColorDodge => BlendMode::ColorDodge,
+            PyBlendMode::ColorBurn => BlendMode::ColorBurn,
+            PyBlendMode::HardLight => BlendMode::HardLight,
+            PyBlendMode::SoftLight => BlendMode::SoftLight,
+            PyBlendMode::Difference => BlendMode::Difference,
+            PyBlendMode::Exclusion => BlendMode::Exclusion,
+            PyBlendMode::Hue => BlendMode::Hue,
+            PyBlendMode::Saturation => BlendMode::Saturation,
+            PyBlendMode::Color => BlendMode::Color,
+            PyBlendMode::Luminosity => BlendMode::Luminosity,
+        }
+    }
+}
+
+/// Python-facing mirror of `CompositeOp`.
+#[pyclass(
+    name = "CompositeOp",
+    module = "codimate",
+    eq,
+    eq_int,
+    frozen,
+    from_py_object
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PyCompositeOp {
+    #[pyo3(name = "CLEAR")]
+    Clear,
+    #[pyo3(name = "COPY")]
+    Copy,
+    #[pyo3(name = "SOURCE_OVER")]
+    SourceOver,
+    #[pyo3(name = "SOURCE_IN")]
+    SourceIn,
+    #[pyo3(name = "SOURCE_OUT")]
+    SourceOut,
+    #[pyo3(name = "SOURCE_ATOP")]
+    SourceAtop,
+    #[pyo3(name = "DESTINATION_OVER")]
+    DestinationOver,
+    #[pyo3(name = "DESTINATION_IN")]
+    DestinationIn,
+    #[pyo3(name = "DESTINATION_OUT")]
+    DestinationOut,
+    #[pyo3(name = "DESTINATION_ATOP")]
+    DestinationAtop,
+    #[pyo3(name = "XOR")]
+    Xor,
+    #[pyo3(name = "PLUS_LIGHTER")]
+    PlusLighter,
+}
+
+impl From<PyCompositeOp> for CompositeOp {
+    fn from(op: PyCompositeOp) -> Self {
+        match op {
+            PyCompositeOp::Clear => CompositeOp::Clear,
+            PyCompositeOp::Copy => CompositeOp::Copy,
+            PyCompositeOp::SourceOver => CompositeOp::SourceOver,
+            PyCompositeOp::SourceIn => CompositeOp::SourceIn,
+            PyCompositeOp::SourceOut => CompositeOp::SourceOut,
+            PyCompositeOp::SourceAtop => CompositeOp::SourceAtop,
+            PyCompositeOp::DestinationOver => CompositeOp::DestinationOver,
+            PyCompositeOp::DestinationIn => CompositeOp::DestinationIn,
+            PyCompositeOp::DestinationOut => CompositeOp::DestinationOut,
+            PyCompositeOp::DestinationAtop => CompositeOp::DestinationAtop,
+            PyCompositeOp::Xor => CompositeOp::Xor,
+            PyCompositeOp::PlusLighter => CompositeOp::PlusLighter,
+        }
+    }
+}
+
+/// Python-facing mirror of `ColorVisionDeficiency`.
+#[pyclass(
+    name = "ColorVisionDeficiency",
+    module = "codimate",
+    eq,
+    eq_int,
+    frozen,
+    from_py_object
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PyColorVisionDeficiency {
+    #[pyo3(name = "PROTANOPIA")]
+    Protanopia,
+    #[pyo3(name = "DEUTERANOPIA")]
+    Deuteranopia,
+    #[pyo3(name = "TRITANOPIA")]
+    Tritanopia,
+    #[pyo3(name = "ACHROMATOPSIA")]
+    Achromatopsia,
+}
+
+impl From<PyColorVisionDeficiency> for ColorVisionDeficiency {
+    fn from(deficiency: PyColorVisionDeficiency) -> Self {
+        match deficiency {
+            PyColorVisionDeficiency::Protanopia => ColorVisionDeficiency::Protanopia,
+            PyColorVisionDeficiency::Deuteranopia => ColorVisionDeficiency::Deuteranopia,
+            PyColorVisionDeficiency::Tritanopia => ColorVisionDeficiency::Tritanopia,
+            PyColorVisionDeficiency::Achromatopsia => ColorVisionDeficiency::Achromatopsia,
+        }
+    }
+}
+
+/// An sRGB u8 color with straight alpha.
+#[pyclass(name = "Color", module = "codimate", eq, hash, frozen, from_py_object)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PyColor(pub Color);
+
+impl From<Color> for PyColor {
+    fn from(color: Color) -> Self {
+        Self(color)
+    }
+}
+
+impl From<PyColor> for Color {
+    fn from(color: PyColor) -> Self {
+        color.0
+    }
+}
+
+// pyo3 methods always borrow self, even for Copy wrappers
+#[allow(clippy::wrong_self_convention)]
+#[pymethods]
+impl PyColor {
+    #[new]
+    #[pyo3(signature = (r, g, b, a = 255))]
+    fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self(Color::new(r, g, b, a))
+    }
+
+    /// Parse a color from a string such as "#ff8800", "rgb(255 136 0)" or "orange".
+    #[staticmethod]
+    fn parse(s: &str) -> PyResult<Self> {
+        Ok(Self(parse_color(s)?))
+    }
+
+    #[classattr]
+    const TRANSPARENT: Self = Self(Color::TRANSPARENT);
+    #[classattr]
+    const BLACK: Self = Self(Color::BLACK);
+    #[classattr]
+    const RED: Self = Self(Color::RED);
+    #[classattr]
+    const GREEN: Self = Self(Color::GREEN);
+    #[classattr]
+    const BLUE: Self = Self(Color::BLUE);
+    #[classattr]
+    const WHITE: Self = Self(Color::WHITE);
+
+    #[getter]
+    fn r(&self) -> u8 {
+        self.0.into_rgba()[0]
+    }
+
+    #[getter]
+    fn g(&self) -> u8 {
+        self.0.into_rgba()[1]
+    }
+
+    #[getter]
+    fn b(&self) -> u8 {
+        self.0.into_rgba()[2]
+    }
+
+    #[getter]
+    fn a(&self) -> u8 {
+        self.0.into_rgba()[3]
+    }
+
+    /// The color as an `(r, g, b, a)` tuple.
+    fn rgba(&self) -> (u8, u8, u8, u8) {
+        let [r, g, b, a] = self.0.into_rgba();
+        (r, g, b, a)
+    }
+
+    fn with_alpha(&self, a: u8) -> Self {
+        Self(self.0.with_alpha(a))
+    }
+
+    /// Interpolate towards `other` in sRGB space.
+    fn lerp(&self, other: PyColor, t: ColorFloat) -> Self {
+        Self(self.0.lerp(other.0, t))
+    }
+
+    /// Interpolate towards `other` in linear space.
+    fn lerp_linear(&self, other: PyColor, t: ColorFloat) -> Self {
+        Self(self.0.lerp_linear(other.0, t))
+    }
+
+    /// Interpolate towards `other` in OKLCH space.
+    fn lerp_oklch(&self, other: PyColor, t: ColorFloat) -> Self {
+        Self(self.0.lerp_oklch(other.0, t))
+    }
+
+    /// Porter-Duff source-over in linear space.
+    fn over(&self, bg: PyColor) -> Self {
+        Self(self.0.over(bg.0))
+    }
+
+    /// Blend this color over a backdrop with the given blend mode.
+    #[pyo3(signature = (bg, mode = PyBlendMode::Normal))]
+    fn blend_over(&self, bg: PyColor, mode: PyBlendMode) -> Self {
+        Self(self.0.blend_over(bg.0, mode.into()))
+    }
+
+    /// Composite this color with a backdrop using a Porter-Duff operator and a blend mode.
+    #[pyo3(signature = (bg, op = PyCompositeOp::SourceOver, mode = PyBlendMode::Normal))]
+    fn composite(&self, bg: PyColor, op: PyCompositeOp, mode: PyBlendMode) -> Self {
+        Self(self.0.composite(bg.0, op.into(), mode.into()))
+    }
+
+    /// The color as CSS `rgb()`. `precision` is the number of decimals for alpha.
+    #[pyo3(signature = (precision = None))]
+    fn to_css_rgb(&self, precision: Option<usize>) -> String {
+        match precision {
+            Some(p) => format!("{:.p$}", self.0.to_css_rgb()),
+            None => self.0.to_css_rgb().to_string(),
+        }
+    }
+
+    /// The color as CSS `hsl()`. `precision` is the number of decimals per component.
+    #[pyo3(signature = (precision = None))]
+    fn to_css_hsl(&self, precision: Option<usize>) -> String {
+        match precision {
+            Some(p) => format!("{:.p$}", self.0.to_css_hsl()),
+            None => self.0.to_css_hsl().to_string(),
+        }
+    }
+
+    /// The color as CSS `oklch()`. `precision` is the number of decimals per component.
+    #[pyo3(signature = (precision = None))]
+    fn to_css_oklch(&self, precision: Option<usize>) -> String {
+        match precision {
+            Some(p) => format!("{:.p$}", self.0.to_css_oklch()),
+            None => self.0.to_css_oklch().to_string(),
+        }
+    }
+
+    /// The CSS name of this color (e.g. "rebeccapurple"), or `None`.
+    fn css_name(&self) -> Option<&'static str> {
+        self.0.css_name()
+    }
+
+    fn relative_luminance(&self) -> ColorFloat {
+        self.0.relative_luminance()
+    }
+
+    fn contrast_ratio(&self, other: PyColor) -> ColorFloat {
+        self.0.contrast_ratio(other.0)
+    }
+
+    /// The color of a blackbody at `kelvin` (1000 to 40000), as bright as possible.
+    #[staticmethod]
+    fn from_kelvin(kelvin: ColorFloat) -> Self {
+        Self(Color::from_kelvin(kelvin))
+    }
+
+    /// Shift this color's white balance from one color temperature (kelvin) to another.
+    fn white_balance(&self, from_kelvin: ColorFloat, to_kelvin: ColorFloat) -> Self {
+        Self(self.0.white_balance(from_kelvin, to_kelvin))
+    }
+
+    /// How this color looks with a color vision deficiency. `severity` goes from 0.0 to 1.0.
+    #[pyo3(signature = (deficiency, severity = 1.0))]
+    fn simulate_cvd(&self, deficiency: PyColorVisionDeficiency, severity: ColorFloat) -> Self {
+        Self(self.0.simulate_cvd(deficiency.into(), severity))
+    }
+
+    /// CIEDE2000 difference to `other`. About 1.0 is just noticeable.
+    fn delta_e_2000(&self, other: PyColor) -> ColorFloat {
+        self.0.delta_e_2000(other.0)
+    }
+
+    /// Euclidean OKLAB difference to `other`. About 0.02 is just noticeable.
+    fn delta_e_ok(&self, other: PyColor) -> ColorFloat {
+        self.0.delta_e_ok(other.0)
+    }
+
+    /// APCA lightness contrast (Lc) of this color as text on `bg`.
+    fn apca_contrast(&self, bg: PyColor) -> ColorFloat {
+        self.0.apca_contrast(bg.0)
+    }
+
+    /// The candidate with the most APCA contrast against `bg`, or `None` if there are none.
+    #[staticmethod]
+    fn best_text_color(bg: PyColor, candidates: Vec<PyColor>) -> Option<Self> {
+        let candidates: Vec<Color> = candidates.into_iter().map(|c| c.0).collect();
+        Color::best_text_color(bg.0, &candidates).map(Self)
+    }
+
+    /// Move OKLCH lightness until the WCAG contrast ratio against `bg` reaches `target`.
+    fn adjust_for_contrast(&self, bg: PyColor, target: ColorFloat) -> Self {
+        Self(self.0.adjust_for_contrast(bg.0, target))
+    }
+
+    /// Move OKLCH lightness until the APCA |Lc| against `bg` reaches `target`.
+    fn adjust_for_apca(&self, bg: PyColor, target: ColorFloat) -> Self {
+        Self(self.0.adjust_for_apca(bg.0, target))
+    }
+
+    /// Rotate the OKLCH hue by `degrees`, keeping lightness and chroma.
+    fn rotate_hue(&self, degrees: ColorFloat) -> Self {
+        Self(self.0.rotate_hue(degrees))
+    }
+
+    /// This color and its complement.
+    fn complementary(&self) -> Vec<Self> {
+        self.0.complementary().map(Self).to_vec()
+    }
+
+    /// This color and its neighbors `spread` degrees either side.
+    #[pyo3(signature = (spread = 30.0))]
+    fn analogous(&self, spread: ColorFloat) -> Vec<Self> {
+        self.0.analogous(spread).map(Self).to_vec()
+    }
+
+    /// This color and the colors 120 and 240 degrees away.
+    fn triadic(&self) -> Vec<Self> {
+        self.0.triadic().map(Self).to_vec()
+    }
+
+    /// This color and the colors 90, 180 and 270 degrees away.
+    fn tetradic(&self) -> Vec<Self> {
+        self.0.tetradic().map(Self).to_vec()
+    }
+
+    /// This color and the colors `spread` degrees either side of its complement.
+    #[pyo3(signature = (spread = 30.0))]
+    fn split_complementary(&self, spread: ColorFloat) -> Vec<Self> {
+        self.0.split_complementary(spread).map(Self).to_vec()
+    }
+
+    /// `n` shades with evenly stepped OKLCH lightness, from `lightest` to `darkest`.
+    #[pyo3(signature = (n, lightest = 0.97, darkest = 0.25))]
+    fn tonal_scale(&self, n: usize, lightest: ColorFloat, darkest: ColorFloat) -> Vec<Self> {
+        self.0.tones(n, lightest, darkest).map(Self).collect()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        let [r, g, b, a] = self.0.into_rgba();
+        format!("Color({r}, {g}, {b}, {a})")
+    }
+}
+
+/// Parse a color from a string. Raises `ColorParseError` on failure.
+#[pyfunction(name = "parse_color")]
+pub fn py_parse_color(s: &str) -> PyResult<PyColor> {
+    PyColor::parse(s)
+}
@@ -0,0 +1,5 @@
+// python-facing wrappers around the rust types.
+// the wrappers are thin on purpose: logic lives in color/renderer, this just converts.
+
+pub mod color;
+pub mod renderer;
@@ -0,0 +1,378 @@
+use std::path::PathBuf;
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+
+use crate::{
+    bindings::color::{PyColor, PyColorVisionDeficiency},
+    color::ColorFloat,
+    renderer::{
+        conversions::{PixelFormat, YuvRange},
+        frame::{Frame, FrameError},
+        io::ImageIoError,
+        render::Renderer,
+        view::{FrameViewMut, clip},
+    },
+};
+
+pyo3::create_exception!(
+    codimate,
+    PyFrameError,
+    PyValueError,
+    "Raised when a frame can't be created with the given dimensions."
+);
+
+impl From<FrameError> for PyErr {
+    fn from(err: FrameError) -> Self {
+        PyFrameError::new_err(err.to_string())
+    }
+}
+
+impl From<ImageIoError> for PyErr {
+    fn from(err: ImageIoError) -> Self {
+        match err {
+            // pyo3 maps io errors onto the matching OSError subclass (FileNotFoundError etc)
+            ImageIoError::Io(err) => err.into(),
+            ImageIoError::Frame(err) => err.into(),
+            err @ ImageIoError::Image(_) => PyValueError::new_err(err.to_string()),
+        }
+    }
+}
+
+/// Python-facing mirror of `PixelFormat`. The YUV range is passed separately.
+#[pyclass(
+    name = "PixelFormat",
+    module = "codimate",
+    eq,
+    eq_int,
+    frozen,
+    from_py_object
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PyPixelFormat {
+    #[pyo3(name = "RGBA8888")]
+    Rgba8888,
+    #[pyo3(name = "RGB24")]
+    Rgb24,
+    #[pyo3(name = "BGRA8888")]
+    Bgra8888,
+    #[pyo3(name = "YUV420P")]
+    Yuv420p,
+}
+
+/// Python-facing mirror of `YuvRange`.
+#[pyclass(
+    name = "YuvRange",
+    module = "codimate",
+    eq,
+    eq_int,
+    frozen,
+    from_py_object
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PyYuvRange {
+    #[pyo3(name = "LIMITED")]
+    Limited,
+    #[pyo3(name = "FULL")]
+    Full,
+}
+
+impl From<PyYuvRange> for YuvRange {
+    fn from(range: PyYuvRange) -> Self {
+        match range {
+            PyYuvRange::Limited => YuvRange::Limited,
+            PyYuvRange::Full => YuvRange::Full,
+        }
+    }
+}
+
+/// Combine the Python format and range arguments into a `PixelFormat`.
+fn pixel_format(format: PyPixelFormat, range: PyYuvRange) -> PixelFormat {
+    match format {
+        PyPixelFormat::Rgba8888 => PixelFormat::Rgba8888,
+        PyPixelFormat::Rgb24 => PixelFormat::Rgb24,
+        PyPixelFormat::Bgra8888 => PixelFormat::Bgra8888,
+        PyPixelFormat::Yuv420p => PixelFormat::Yuv420p(range.into()),
+    }
+}
+
+/// An RGBA8888 frame buffer.
+#[pyclass(name = "Frame", module = "codimate")]
+pub struct PyFrame(pub Frame);
+
+#[pymethods]
+impl PyFrame {
+    #[new]
+    fn new(width: u32, height: u32) -> PyResult<Self> {
+        Ok(Self(Frame::try_new(width, height)?))
+    }
+
+    #[getter]
+    fn width(&self) -> u32 {
+        self.0.width()
+    }
+
+    #[getter]
+    fn height(&self) -> u32 {
+        self.0.height()
+    }
+
+    /// The pixel at (x, y), or `None` if it's out of bounds.
+    fn get_pixel(&self, x: u32, y: u32) -> Option<PyColor> {
+        self.0.get_pixel(x, y).map(PyColor)
+    }
+
+    /// Build a frame from raw pixel bytes in `format`. `range` only matters for YUV.
+    #[staticmethod]
+    #[pyo3(signature = (width, height, data, format = PyPixelFormat::Rgba8888, range = PyYuvRange::Limited))]
+    fn from_bytes(
+        width: u32,
+        height: u32,
+        data: &[u8],
+        format: PyPixelFormat,
+        range: PyYuvRange,
+    ) -> PyResult<Self> {
+        Ok(Self(Frame::from_pixels(
+            width,
+            height,
+            pixel_format(format, range),
+            data,
+        )?))
+    }
+
+    /// A copy of the pixels as bytes in `format` (RGBA row by row by default).
+    /// `range` only matters for YUV.
+    #[pyo3(signature = (format = PyPixelFormat::Rgba8888, range = PyYuvRange::Limited))]
+    fn to_bytes<'py>(
+        &self,
+        py: Python<'py>,
+        format: PyPixelFormat,
+        range: PyYuvRange,
+    ) -> Bound<'py, PyBytes> {
+        match pixel_format(format, range) {
+            PixelFormat::Rgba8888 => PyBytes::new(py, self.0.as_slice()),
+            format => PyBytes::new(py, &self.0.to_pixels(format)),
+        }
+    }
+
+    /// Load an image file (PNG, JPEG, QOI, PPM, ...). The format is detected from the contents.
+    #[staticmethod]
+    fn from_image(path: PathBuf) -> PyResult<Self> {
+        Ok(Self(Frame::from_image(path)?))
+    }
+
+    /// Decode an image file's contents that are already in memory.
+    #[staticmethod]
+    fn decode_image(data: &[u8]) -> PyResult<Self> {
+        Ok(Self(Frame::decode_image(data)?))
+    }
+
+    /// Save the frame as a PNG.
+    fn save_png(&self, path: PathBuf) -> PyResult<()> {
+        Ok(self.0.save_png(path)?)
+    }
+
+    /// The frame encoded as PNG bytes.
+    fn encode_png<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &self.0.encode_png()?))
+    }
+
+    /// Save the frame as a QOI image: lossless, keeps alpha, much faster than PNG.
+    fn save_qoi(&self, path: PathBuf) -> PyResult<()> {
+        Ok(self.0.save_qoi(path)?)
+    }
+
+    /// The frame encoded as QOI bytes.
+    fn encode_qoi<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.encode_qoi())
+    }
+
+    /// Save the frame as a binary PPM. Alpha is dropped.
+    fn save_ppm(&self, path: PathBuf) -> PyResult<()> {
+        Ok(self.0.save_ppm(path)?)
+    }
+
+    /// The frame encoded as binary PPM bytes. Alpha is dropped.
+    fn encode_ppm<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.encode_ppm())
+    }
+
+    /// A rectangle of the frame to draw into with coordinates relative to its top left
+    /// corner. The rectangle is clipped to the frame.
+    fn view(slf: &Bound<'_, Self>, x: u32, y: u32, width: u32, height: u32) -> PyFrameView {
+        let frame = slf.borrow();
+        let (x, y, width, height) = clip(x, y, width, height, frame.0.width(), frame.0.height());
+        PyFrameView {
+            frame: slf.clone().unbind(),
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Lets Jupyter display frames inline.
+    fn _repr_png_<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        self.encode_png(py)
+    }
+
+    /// Recolor the frame in place as seen with a color vision deficiency.
+    #[pyo3(signature = (deficiency, severity = 1.0))]
+    fn simulate_cvd(&mut self, deficiency: PyColorVisionDeficiency, severity: ColorFloat) {
+        self.0.simulate_cvd(deficiency.into(), severity);
+    }
+
+    /// Shift the frame's white balance from one color temperature (kelvin) to another.
+    fn white_balance(&mut self, from_kelvin: ColorFloat, to_kelvin: ColorFloat) {
+        self.0.white_balance(from_kelvin, to_kelvin);
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Frame({}, {})", self.0.width(), self.0.height())
+    }
+}
+
+/// A rectangle of a frame, made by `Frame.view`. Renderer calls drawing into it use
+/// coordinates relative to its top left corner and never draw outside it.
+#[pyclass(name = "FrameView", module = "codimate", frozen)]
+pub struct PyFrameView {
+    frame: Py<PyFrame>,
+    // position and size in the frame, already clipped
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+#[pymethods]
+impl PyFrameView {
+    /// The frame this view draws into.
+    #[getter]
+    fn frame(&self, py: Python<'_>) -> Py<PyFrame> {
+        self.frame.clone_ref(py)
+    }
+
+    /// The view's left edge in the frame.
+    #[getter]
+    fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// The view's top edge in the frame.
+    #[getter]
+    fn y(&self) -> u32 {
+        self.y
+    }
+
+    #[getter]
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[getter]
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The pixel at (x, y) relative to the view, or `None` if it's outside the view.
+    fn get_pixel(&self, py: Python<'_>, x: u32, y: u32) -> PyResult<Option<PyColor>> {
+        let frame = self.frame.bind(py).try_borrow()?;
+        let view = frame.0.view(self.x, self.y, self.width, self.height);
+        Ok(view.get_pixel(x, y).map(PyColor))
+    }
+
+    /// A smaller view inside this one. (x, y) is relative to this view and the
+    /// rectangle is clipped to it.
+    fn view(&self, py: Python<'_>, x: u32, y: u32, width: u32, height: u32) -> PyFrameView {
+        let (x, y, width, height) = clip(x, y, width, height, self.width, self.height);
+        PyFrameView {
+            frame: self.frame.clone_ref(py),
+            x: self.x + x,
+            y: self.y + y,
+            width,
+            height,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "FrameView({}, {}, {}, {})",
+            self.x, self.y, self.width, self.height
+        )
+    }
+}
+
+/// Anything the renderer can draw into: a whole frame or a view of one.
+#[derive(FromPyObject)]
+pub enum DrawTarget<'py> {
+    Frame(PyRefMut<'py, PyFrame>),
+    View(Bound<'py, PyFrameView>),
+}
+
+impl DrawTarget<'_> {
+    /// Borrow the target's pixels and draw into them.
+    fn draw(self, f: impl FnOnce(FrameViewMut<'_>)) -> PyResult<()> {
+        match self {
+            DrawTarget::Frame(mut frame) => f(frame.0.as_view_mut()),
+            DrawTarget::View(view) => {
+                let v = view.get();
+                let mut frame = v.frame.bind(view.py()).try_borrow_mut()?;
+                f(frame.0.view_mut(v.x, v.y, v.width, v.height));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Draws into frames. Every drawing call takes the frame (or frame view) to draw into.
+#[pyclass(name = "Renderer", module = "codimate")]
+pub struct PyRenderer(pub Renderer);
+
+#[pymethods]
+impl PyRenderer {
+    #[new]
+    fn new() -> Self {
+        Self(Renderer::new(Vec::new()))
+    }
+
+    /// Fill the whole frame (or view) with one color.
+    fn clear(&mut self, target: DrawTarget<'_>, color: PyColor) -> PyResult<()> {
+        target.draw(|fb| self.0.clear(fb, color.0))
+    }
+
+    /// Write a single pixel. Out of bounds writes are ignored.
+    fn set_pixel(
+        &mut self,
+        target: DrawTarget<'_>,
+        x: u32,
+        y: u32,
+        color: PyColor,
+    ) -> PyResult<()> {
+        target.draw(|fb| self.0.set_pixel(fb, x, y, color.0))
+    }
+
+    /// Fill row `y` from `x0` up to (but not including) `x1`.
+    fn hspan(
+        &mut self,
+        target: DrawTarget<'_>,
+        y: u32,
+        x0: u32,
+        x1: u32,
+        color: PyColor,
+    ) -> PyResult<()> {
+        target.draw(|fb| self.0.hspan(fb, y, x0, x1, color.0))
+    }
+
+    /// Fill a solid rectangle, clipped to the frame (or view).
+    #[allow(clippy::too_many_arguments)]
+    fn rect(
+        &mut self,
+        target: DrawTarget<'_>,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        color: PyColor,
+    ) -> PyResult<()> {
+        target.draw(|fb| self.0.rect(fb, x, y, width, height, color.0))
+    }
+}
@@ -0,0 +1,212 @@
+#![allow(dead_code)]
+
+// Bulk blending over RGBA8888 byte rows.
+// the blend mode is matched once per call and each arm runs its own copy of the pixel
+// loop with the mode fixed, so the per-pixel `match` in `Color::blend_over` folds away.
+// every pixel still goes through `Color::blend_over` itself, which keeps the output
+// bit-identical to blending pixel by pixel (including the `srgb_lut` tables when on).
+// that also means each pixel is still linearised in floats: this only removes the
+// per-pixel dispatch, it is not a faster blend kernel.
+
+use crate::color::{
+    ColorFloat,
+    model::{BlendMode, Color},
+};
+
+/// Run `$body` with `$m` bound to `$mode`, spelled out as a literal variant.
+///
+/// Each arm is its own copy of the body, so the compiler sees a fixed mode in the loop.
+macro_rules! for_each_mode {
+    ($mode:expr, |$m:ident| $body:block) => {
+        for_each_mode!(@arms $mode, $m, $body,
+            Normal, Multiply, Screen, Overlay, Darken, Lighten, ColorDodge, ColorBurn,
+            HardLight, SoftLight, Difference, Exclusion, Hue, Saturation, Color, Luminosity)
+    };
+    (@arms $mode:expr, $m:ident, $body:block, $($variant:ident),*) => {
+        match $mode {
+            $(BlendMode::$variant => {
+                let $m = BlendMode::$variant;
+                $body
+            })*
+        }
+    };
+}
+
+/// Blend a row of RGBA pixels over another with a blend mode.
+///
+/// For every pixel this computes
+/// `src.scale_alpha(opacity).blend_over(dst, mode)` and writes it back into `dst`.
+/// Pixels are processed up to the shorter of the two slices, and trailing bytes
+/// that don't make up a whole pixel are left alone.
+///
+/// The output is bit-identical to calling `Color::blend_over` per pixel. Only the
+/// per-pixel mode dispatch is saved; each pixel still costs a full `blend_over`.
+///
+/// # Arguments
+///
+/// - `dst` (`&mut [u8]`) - The backdrop row, RGBA8888. Overwritten with the result.
+/// - `src` (`&[u8]`) - The source row, RGBA8888.
+/// - `mode` (`BlendMode`) - The blend mode to use.
+/// - `opacity` (`ColorFloat`) - Extra opacity applied to every source pixel.
+///   This value will be clamped between and including 0.0 and 1.0.
+///
+/// # Examples
+///
+/// ```
+/// use codimate::color::{BlendMode, blend::blend_row};
+///
+/// let mut dst = [255, 255, 255, 255, 0, 0, 0, 255];
+/// let src = [255, 0, 0, 255, 0, 0, 255, 128];
+/// blend_row(&mut dst, &src, BlendMode::Multiply, 1.0);
+/// ```
+pub fn blend_row(dst: &mut [u8], src: &[u8], mode: BlendMode, opacity: ColorFloat) {
+    if opacity.is_nan() || opacity <= 0.0 {
+        return;
+    }
+
+    let pixels = dst.chunks_exact_mut(4).zip(src.chunks_exact(4));
+    for_each_mode!(mode, |m| {
+        for (d, s) in pixels {
+            let s = Color::from_rgba([s[0], s[1], s[2], s[3]]).scale_alpha(opacity);
+            blend_pixel(d, s, m);
+        }
+    });
+}
+
+/// Blend one color over a whole row of RGBA pixels with a blend mode.
+///
+/// This is `blend_row` with every source pixel set to `color`.
+///
+/// # Arguments
+///
+/// - `dst` (`&mut [u8]`) - The backdrop row, RGBA8888. Overwritten with the result.
+/// - `color` (`Color`) - The source color.
+/// - `mode` (`BlendMode`) - The blend mode to use.
+/// - `opacity` (`ColorFloat`) - Extra opacity applied to `color`.
+///   This value will be clamped between and including 0.0 and 1.0.
+///
+/// # Examples
+///
+/// ```
+/// use codimate::color::{BlendMode, Color, blend::blend_row_color};
+///
+/// let mut row = [0u8; 4 * 1920];
+/// blend_row_color(&mut row, Color::new(255, 215, 0, 255), BlendMode::Screen, 0.5);
+/// ```
+pub fn blend_row_color(dst: &mut [u8], color: Color, mode: BlendMode, opacity: ColorFloat) {
+    if opacity.is_nan() || opacity <= 0.0 {
+        return;
+    }
+
+    let color = color.scale_alpha(opacity);
+    if color.into_rgba()[3] == 0 {
+        return;
+    }
+
+    let pixels = dst.chunks_exact_mut(4);
+    for_each_mode!(mode, |m| {
+        for d in pixels {
+            blend_pixel(d, color, m);
+        }
+    });
+}
+
+/// Blend `src` over the pixel in `dst` and write it back.
+#[inline(always)]
+fn blend_pixel(dst: &mut [u8], src: Color, mode: BlendMode) {
+    // a fully transparent source leaves the backdrop untouched
+    if src.into_rgba()[3] == 0 {
+        return;
+    }
+    let bg = Color::from_rgba([dst[0], dst[1], dst[2], dst[3]]);
+    dst.copy_from_slice(&src.blend_over(bg, mode).into_rgba());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small xorshift so the rows are varied but reproducible.
+    fn noise(seed: &mut u32) -> u8 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 17;
+        *seed ^= *seed << 5;
+        (*seed >> 24) as u8
+    }
+
+    /// A row of random pixels, with every source alpha class represented.
+    fn row(seed: &mut u32, pixels: usize) -> Vec<u8> {
+        let mut row = Vec::with_capacity(pixels * 4);
+        for i in 0..pixels {
+            row.extend([noise(seed), noise(seed), noise(seed)]);
+            row.push(match i % 4 {
+                0 => 0,
+                1 => 255,
+                _ => noise(seed),
+            });
+        }
+        row
+    }
+
+    fn pixel(bytes: &[u8]) -> Color {
+        Color::from_rgba([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    #[test]
+    fn blend_row_matches_blend_over() {
+        let mut seed = 0x9e37_79b9;
+        for mode in BlendMode::ALL {
+            for opacity in [1.0, 0.6, 0.25] {
+                let src = row(&mut seed, 64);
+                let dst = row(&mut seed, 64);
+                let mut out = dst.clone();
+                blend_row(&mut out, &src, mode, opacity);
+
+                for ((o, s), d) in out.chunks(4).zip(src.chunks(4)).zip(dst.chunks(4)) {
+                    let expected = pixel(s).scale_alpha(opacity).blend_over(pixel(d), mode);
+                    assert_eq!(pixel(o), expected, "{mode:?} at opacity {opacity}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blend_row_color_matches_blend_over() {
+        let mut seed = 0x2545_f491;
+        for mode in BlendMode::ALL {
+            for opacity in [1.0, 0.6, 0.25] {
+                for a in [0, 255, 1, 128, 200] {
+                    let color = Color::new(noise(&mut seed), noise(&mut seed), noise(&mut seed), a);
+                    let dst = row(&mut seed, 32);
+                    let mut out = dst.clone();
+                    blend_row_color(&mut out, color, mode, opacity);
+
+                    for (o, d) in out.chunks(4).zip(dst.chunks(4)) {
+                        let expected = color.scale_alpha(opacity).blend_over(pixel(d), mode);
+                        assert_eq!(
+                            pixel(o),
+                            expected,
+                            "{mode:?} {color:?} at opacity {opacity}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blend_row_leaves_trailing_bytes() {
+        let mut dst = [10, 20, 30, 255, 1, 2];
+        blend_row(
+            &mut dst,
+            &[200, 100, 0, 255, 9, 9, 9],
+            BlendMode::Normal,
+            1.0,
+        );
+        assert_eq!(dst, [200, 100, 0, 255, 1, 2]);
+
+        let mut dst = [10, 20, 30, 255];
+        blend_row_color(&mut dst, Color::WHITE, BlendMode::Normal, 0.0);
+        assert_eq!(dst, [10, 20, 30, 255]);
+    }
+}
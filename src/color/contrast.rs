@@ -0,0 +1,337 @@
+#![allow(dead_code)]
+
+// Contrast helpers for keeping text readable: the APCA lightness contrast from the
+// WCAG 3 drafts, picking the most readable of a set of text colors, and nudging a
+// color's OKLCH lightness until it reaches a target contrast against a background.
+//
+// APCA constants are from APCA-W3 0.0.98G-4g: https://github.com/Myndex/apca-w3
+
+#[cfg(not(feature = "std"))]
+use crate::color::math::FloatMath;
+use crate::color::{ColorFloat, model::Color};
+
+// sRGB -> screen luminance
+const MAIN_TRC: ColorFloat = 2.4;
+const S_R_CO: ColorFloat = 0.2126729;
+const S_G_CO: ColorFloat = 0.7151522;
+const S_B_CO: ColorFloat = 0.0721750;
+
+// soft clamp for near-black luminances
+const BLK_THRS: ColorFloat = 0.022;
+const BLK_CLMP: ColorFloat = 1.414;
+
+// exponents for normal (dark on light) and reverse (light on dark) polarity
+const NORM_BG: ColorFloat = 0.56;
+const NORM_TXT: ColorFloat = 0.57;
+const REV_TXT: ColorFloat = 0.62;
+const REV_BG: ColorFloat = 0.65;
+
+const SCALE: ColorFloat = 1.14;
+const LO_OFFSET: ColorFloat = 0.027;
+const LO_CLIP: ColorFloat = 0.1;
+const DELTA_Y_MIN: ColorFloat = 0.0005;
+
+/// How many halvings the lightness search does. 2^-24 is well below one u8 step.
+const SEARCH_STEPS: usize = 24;
+
+impl Color {
+    /// Calculate the APCA lightness contrast (Lc) of text on a background.
+    ///
+    /// Unlike `Color::contrast_ratio` this is polarity aware: dark text on a light
+    /// background gives a positive Lc and light text on a dark background a negative
+    /// one. Roughly, |Lc| 90 is preferred for body text, 75 is the minimum for body
+    /// text, 60 for other content text, and 45 for large or bold headlines.
+    ///
+    /// Alpha is ignored; composite translucent text over its background first.
+    ///
+    /// Source: https://github.com/Myndex/apca-w3
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The text color.
+    /// - `bg` (`Color`) - The background color.
+    ///
+    /// # Returns
+    ///
+    /// - `ColorFloat` - The Lc value, about -108 to 106.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let gray = Color::new(136, 136, 136, 255);
+    /// let lc = gray.apca_contrast(Color::WHITE); // about 63.1
+    /// ```
+    #[must_use]
+    pub fn apca_contrast(self, bg: Color) -> ColorFloat {
+        let y_txt = apca_luminance(self);
+        let y_bg = apca_luminance(bg);
+
+        if (y_bg - y_txt).abs() < DELTA_Y_MIN {
+            return 0.0;
+        }
+
+        let lc = if y_bg > y_txt {
+            // dark text on a light background
+            let sapc = (y_bg.powf(NORM_BG) - y_txt.powf(NORM_TXT)) * SCALE;
+            if sapc < LO_CLIP {
+                0.0
+            } else {
+                sapc - LO_OFFSET
+            }
+        } else {
+            // light text on a dark background
+            let sapc = (y_bg.powf(REV_BG) - y_txt.powf(REV_TXT)) * SCALE;
+            if sapc > -LO_CLIP {
+                0.0
+            } else {
+                sapc + LO_OFFSET
+            }
+        };
+
+        lc * 100.0
+    }
+
+    /// Pick the most readable text color for a background.
+    ///
+    /// Candidates are ranked by the magnitude of their APCA contrast against `bg`.
+    /// Ties go to the earlier candidate.
+    ///
+    /// # Arguments
+    ///
+    /// - `bg` (`Color`) - The background color.
+    /// - `candidates` (`&[Color]`) - The text colors to choose from.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Color>` - The best candidate, or `None` if there are none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let teal = Color::new(0, 128, 128, 255);
+    /// let text = Color::best_text_color(teal, &[Color::BLACK, Color::WHITE]);
+    /// assert_eq!(text, Some(Color::WHITE));
+    /// ```
+    #[must_use]
+    pub fn best_text_color(bg: Color, candidates: &[Color]) -> Option<Color> {
+        let mut best: Option<(Color, ColorFloat)> = None;
+        for &c in candidates {
+            let lc = c.apca_contrast(bg).abs();
+            if best.is_none_or(|(_, best_lc)| lc > best_lc) {
+                best = Some((c, lc));
+            }
+        }
+        best.map(|(c, _)| c)
+    }
+
+    /// Change a color's OKLCH lightness as little as possible to reach a WCAG 2.x
+    /// contrast ratio against a background.
+    ///
+    /// Hue, chroma (as far as the gamut allows), and alpha are kept. If the color
+    /// already meets the target it's returned unchanged. If no lightness reaches
+    /// the target, the lightness with the most contrast (black or white) is used.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The text color to adjust.
+    /// - `bg` (`Color`) - The background color.
+    /// - `target` (`ColorFloat`) - The contrast ratio to reach, like 4.5 or 7.0.
+    ///
+    /// # Returns
+    ///
+    /// - `Color` - The adjusted color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let coral = Color::new(255, 127, 80, 255);
+    /// let readable = coral.adjust_for_contrast(Color::WHITE, 4.5);
+    /// assert!(readable.contrast_ratio(Color::WHITE) >= 4.5);
+    /// ```
+    #[must_use]
+    pub fn adjust_for_contrast(self, bg: Color, target: ColorFloat) -> Color {
+        self.adjust_lightness(|c| c.contrast_ratio(bg), target)
+    }
+
+    /// Change a color's OKLCH lightness as little as possible to reach an APCA
+    /// contrast against a background.
+    ///
+    /// Works like `Color::adjust_for_contrast`, but the target is an |Lc| value
+    /// (see `Color::apca_contrast`) and either polarity counts.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The text color to adjust.
+    /// - `bg` (`Color`) - The background color.
+    /// - `target` (`ColorFloat`) - The |Lc| to reach, like 60.0 or 75.0.
+    ///
+    /// # Returns
+    ///
+    /// - `Color` - The adjusted color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let slate = Color::new(112, 128, 144, 255);
+    /// let readable = slate.adjust_for_apca(Color::BLACK, 75.0);
+    /// ```
+    #[must_use]
+    pub fn adjust_for_apca(self, bg: Color, target: ColorFloat) -> Color {
+        self.adjust_lightness(|c| c.apca_contrast(bg).abs(), target)
+    }
+
+    /// Search lightening and darkening for the closest lightness with `contrast >= target`.
+    fn adjust_lightness<F>(self, contrast: F, target: ColorFloat) -> Color
+    where
+        F: Fn(Color) -> ColorFloat,
+    {
+        if contrast(self) >= target {
+            return self;
+        }
+
+        let [l, c, h] = self.into_oklch();
+        let [.., a] = self.into_rgba();
+        // the search lands right on rounding boundaries, so grays need to stay neutral
+        let at = |l: ColorFloat| Color::from_oklch_neutral([l, c, h]).with_alpha(a);
+
+        // for each direction, find the smallest move that meets the target
+        let search = |end: ColorFloat| -> Option<(ColorFloat, Color)> {
+            if contrast(at(end)) < target {
+                return None;
+            }
+            let (mut near, mut far) = (l, end);
+            for _ in 0..SEARCH_STEPS {
+                let mid = (near + far) * 0.5;
+                if contrast(at(mid)) >= target {
+                    far = mid;
+                } else {
+                    near = mid;
+                }
+            }
+            Some(((far - l).abs(), at(far)))
+        };
+
+        match (search(0.0), search(1.0)) {
+            (Some((dark_d, dark)), Some((light_d, light))) => {
+                if dark_d <= light_d {
+                    dark
+                } else {
+                    light
+                }
+            }
+            (Some((_, dark)), None) => dark,
+            (None, Some((_, light))) => light,
+            // unreachable target: settle for whichever extreme is best
+            (None, None) => {
+                let (dark, light) = (at(0.0), at(1.0));
+                if contrast(dark) >= contrast(light) {
+                    dark
+                } else {
+                    light
+                }
+            }
+        }
+    }
+}
+
+/// The APCA screen luminance of a color, with the near-black soft clamp applied.
+fn apca_luminance(color: Color) -> ColorFloat {
+    let [r, g, b, _] = color.into_rgba();
+    let lin = |v: u8| (v as ColorFloat / 255.0).powf(MAIN_TRC);
+    let y = S_R_CO * lin(r) + S_G_CO * lin(g) + S_B_CO * lin(b);
+
+    if y < BLK_THRS {
+        y + (BLK_THRS - y).powf(BLK_CLMP)
+    } else {
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hue_distance(a: ColorFloat, b: ColorFloat) -> ColorFloat {
+        let d = (a - b).rem_euclid(360.0);
+        d.min(360.0 - d)
+    }
+
+    #[test]
+    fn apca_reference_values() {
+        // from the APCA-W3 reference implementation
+        let gray = Color::new(0x88, 0x88, 0x88, 255);
+        assert!((gray.apca_contrast(Color::WHITE) - 63.06).abs() < 0.01);
+        assert!((Color::BLACK.apca_contrast(Color::WHITE) - 106.04).abs() < 0.01);
+        assert!((Color::WHITE.apca_contrast(Color::BLACK) + 107.88).abs() < 0.01);
+        assert_eq!(gray.apca_contrast(gray), 0.0);
+    }
+
+    #[test]
+    fn best_text_color_picks_the_most_contrast() {
+        let navy = Color::new(0, 0, 128, 255);
+        let light = Color::new(255, 255, 224, 255);
+        assert_eq!(
+            Color::best_text_color(navy, &[Color::BLACK, Color::WHITE]),
+            Some(Color::WHITE)
+        );
+        assert_eq!(
+            Color::best_text_color(light, &[Color::WHITE, Color::BLACK]),
+            Some(Color::BLACK)
+        );
+        assert_eq!(Color::best_text_color(navy, &[]), None);
+    }
+
+    #[test]
+    fn adjustment_reaches_the_target_and_keeps_hue() {
+        let backgrounds = [Color::WHITE, Color::BLACK, Color::new(40, 60, 90, 255)];
+        let texts = [
+            Color::new(200, 60, 60, 255),
+            Color::new(60, 160, 90, 255),
+            Color::new(90, 110, 220, 200),
+        ];
+        for bg in backgrounds {
+            for text in texts {
+                let [_, _, h] = text.into_oklch();
+
+                let wcag = text.adjust_for_contrast(bg, 4.5);
+                assert!(wcag.contrast_ratio(bg) >= 4.5, "{text:?} on {bg:?}");
+                assert!(hue_distance(wcag.into_oklch()[2], h) < 3.0, "{wcag:?}");
+                assert_eq!(wcag.into_rgba()[3], text.into_rgba()[3]);
+
+                let apca = text.adjust_for_apca(bg, 60.0);
+                assert!(apca.apca_contrast(bg).abs() >= 60.0, "{text:?} on {bg:?}");
+                assert!(hue_distance(apca.into_oklch()[2], h) < 3.0, "{apca:?}");
+                assert_eq!(apca.into_rgba()[3], text.into_rgba()[3]);
+            }
+        }
+    }
+
+    #[test]
+    fn adjustment_keeps_passing_colors() {
+        let dark = Color::new(30, 30, 80, 255);
+        assert!(dark.contrast_ratio(Color::WHITE) >= 7.0);
+        assert_eq!(dark.adjust_for_contrast(Color::WHITE, 7.0), dark);
+        assert!(dark.apca_contrast(Color::WHITE) >= 90.0);
+        assert_eq!(dark.adjust_for_apca(Color::WHITE, 90.0), dark);
+    }
+
+    #[test]
+    fn grays_stay_gray() {
+        let gray = Color::new(150, 150, 150, 255);
+        for c in [
+            gray.adjust_for_contrast(Color::WHITE, 4.5),
+            gray.adjust_for_apca(Color::WHITE, 75.0),
+        ] {
+            let [r, g, b, _] = c.into_rgba();
+            assert!(r == g && g == b, "{c:?}");
+        }
+    }
+}
@@ -0,0 +1,294 @@
+#![allow(dead_code)]
+
+// CSS serialization. every form written here parses back to the same color through
+// `parse_color` as long as the default precision (or higher) is used.
+// lower precisions are fine for display but can be off by one in a channel.
+// these are Display wrappers rather than String builders so they work without alloc.
+
+use core::fmt::{self, Write};
+
+#[cfg(not(feature = "std"))]
+use crate::color::math::FloatMath;
+use crate::color::{ColorFloat, model::Color};
+
+/// Default number of decimals for the alpha of `rgb()`.
+const RGB_PRECISION: usize = 3;
+/// Default number of decimals for the components of `hsl()`.
+const HSL_PRECISION: usize = 2;
+/// Default number of decimals for the components of `oklch()`.
+const OKLCH_PRECISION: usize = 5;
+/// More decimals than this are meaningless for `ColorFloat` anyway.
+const MAX_PRECISION: usize = 16;
+
+/// Writes a color as CSS `rgb(r g b)` or `rgb(r g b / a)`.
+///
+/// The formatter precision (`{:.2}`) sets the number of decimals used for alpha.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CssRgb(pub Color);
+
+/// Writes a color as CSS `hsl(h s% l%)` or `hsl(h s% l% / a)`.
+///
+/// The formatter precision (`{:.2}`) sets the number of decimals used for every component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CssHsl(pub Color);
+
+/// Writes a color as CSS `oklch(L C h)` or `oklch(L C h / a)`.
+///
+/// The formatter precision (`{:.5}`) sets the number of decimals used for every component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CssOklch(pub Color);
+
+impl Color {
+    /// Get a CSS `rgb()` representation of a color.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to serialize.
+    ///
+    /// # Returns
+    ///
+    /// - `CssRgb` - A `Display` wrapper that writes the CSS string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_coral = Color::new(255, 127, 80, 128);
+    /// let css = translucent_coral.to_css_rgb().to_string(); // "rgb(255 127 80 / 0.502)"
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_css_rgb(self) -> CssRgb {
+        CssRgb(self)
+    }
+
+    /// Get a CSS `hsl()` representation of a color.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to serialize.
+    ///
+    /// # Returns
+    ///
+    /// - `CssHsl` - A `Display` wrapper that writes the CSS string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let coral = Color::new(255, 127, 80, 255);
+    /// let css = format!("{:.1}", coral.to_css_hsl()); // "hsl(16.1 100% 65.7%)"
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_css_hsl(self) -> CssHsl {
+        CssHsl(self)
+    }
+
+    /// Get a CSS `oklch()` representation of a color.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to serialize.
+    ///
+    /// # Returns
+    ///
+    /// - `CssOklch` - A `Display` wrapper that writes the CSS string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let coral = Color::new(255, 127, 80, 255);
+    /// let css = coral.to_css_oklch().to_string(); // "oklch(0.73511 0.16799 40.24662)"
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_css_oklch(self) -> CssOklch {
+        CssOklch(self)
+    }
+}
+
+impl fmt::Display for CssRgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b, a] = self.0.into_rgba();
+        write!(f, "rgb({r} {g} {b}")?;
+        write_alpha(f, a, f.precision().unwrap_or(RGB_PRECISION))?;
+        f.write_char(')')
+    }
+}
+
+impl fmt::Display for CssHsl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(HSL_PRECISION);
+        let [h, s, l, _] = self.0.into_hsla();
+
+        f.write_str("hsl(")?;
+        write_number(f, h, precision)?;
+        f.write_char(' ')?;
+        write_number(f, s, precision)?;
+        f.write_str("% ")?;
+        write_number(f, l, precision)?;
+        f.write_char('%')?;
+        write_alpha(f, self.0.into_rgba()[3], precision.max(RGB_PRECISION))?;
+        f.write_char(')')
+    }
+}
+
+impl fmt::Display for CssOklch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(OKLCH_PRECISION);
+        let [l, c, h] = self.0.into_oklch();
+        // the hue of a gray is noise, so pin it
+        let h = if round_to(c, precision) == 0.0 {
+            0.0
+        } else {
+            h
+        };
+
+        f.write_str("oklch(")?;
+        write_number(f, l, precision)?;
+        f.write_char(' ')?;
+        write_number(f, c, precision)?;
+        f.write_char(' ')?;
+        write_number(f, h, precision)?;
+        write_alpha(f, self.0.into_rgba()[3], precision.max(RGB_PRECISION))?;
+        f.write_char(')')
+    }
+}
+
+/// Write ` / a` for translucent colors. Opaque colors get nothing.
+fn write_alpha(f: &mut fmt::Formatter<'_>, a: u8, precision: usize) -> fmt::Result {
+    if a == 255 {
+        return Ok(());
+    }
+    f.write_str(" / ")?;
+    write_number(f, a as ColorFloat / 255.0, precision)
+}
+
+/// Round a float to `precision` decimals.
+fn round_to(x: ColorFloat, precision: usize) -> ColorFloat {
+    let scale = (10.0 as ColorFloat).powi(precision as i32);
+    (x * scale).round() / scale
+}
+
+/// Write a float with at most `precision` decimals, trimming trailing zeros
+/// (`0.5` rather than `0.5000`) and never writing `-0`.
+fn write_number(f: &mut fmt::Formatter<'_>, x: ColorFloat, precision: usize) -> fmt::Result {
+    let precision = precision.min(MAX_PRECISION);
+    let mut buf = StackBuf::new();
+    write!(buf, "{x:.precision$}")?;
+
+    let mut s = buf.as_str();
+    if s.contains('.') {
+        s = s.trim_end_matches('0').trim_end_matches('.');
+    }
+    if s == "-0" {
+        s = "0";
+    }
+    f.write_str(s)
+}
+
+/// A small fixed-size buffer for formatting numbers without allocating.
+struct StackBuf {
+    buf: [u8; 64],
+    len: usize,
+}
+
+impl StackBuf {
+    const fn new() -> Self {
+        Self {
+            buf: [0; 64],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // only ever written to through `write_str`, so this is valid UTF-8
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+}
+
+impl Write for StackBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::parse::parse_color;
+
+    const ALPHAS: [u8; 5] = [255, 254, 128, 1, 0];
+
+    /// Every channel value on its own, plus a coarse grid of mixed colors.
+    fn sweep() -> impl Iterator<Item = Color> {
+        let ramps = (0..=255u8).flat_map(|v| {
+            [
+                Color::new(v, 0, 0, 255),
+                Color::new(0, v, 0, 255),
+                Color::new(0, 0, v, 255),
+                Color::new(v, v, v, 255),
+                Color::new(v, 255 - v, v / 2, 255),
+            ]
+        });
+        let grid = (0..=255u8).step_by(15).flat_map(|r| {
+            (0..=255u8).step_by(15).flat_map(move |g| {
+                (0..=255u8)
+                    .step_by(15)
+                    .map(move |b| Color::new(r, g, b, 255))
+            })
+        });
+        ramps.chain(grid).flat_map(|c| {
+            let [r, g, b, _] = c.into_rgba();
+            ALPHAS.map(|a| Color::new(r, g, b, a))
+        })
+    }
+
+    #[test]
+    fn rgb_round_trips() {
+        for c in sweep() {
+            let css = c.to_css_rgb().to_string();
+            assert_eq!(parse_color(&css), Ok(c), "{css}");
+        }
+    }
+
+    #[test]
+    fn hsl_round_trips() {
+        for c in sweep() {
+            let css = c.to_css_hsl().to_string();
+            assert_eq!(parse_color(&css), Ok(c), "{css}");
+        }
+    }
+
+    #[test]
+    fn oklch_round_trips() {
+        for c in sweep() {
+            let css = c.to_css_oklch().to_string();
+            assert_eq!(parse_color(&css), Ok(c), "{css}");
+        }
+    }
+
+    #[test]
+    fn known_strings() {
+        let coral = Color::new(255, 127, 80, 255);
+        assert_eq!(coral.to_css_rgb().to_string(), "rgb(255 127 80)");
+        assert_eq!(
+            Color::new(255, 127, 80, 128).to_css_rgb().to_string(),
+            "rgb(255 127 80 / 0.502)"
+        );
+        assert_eq!(format!("{:.1}", coral.to_css_hsl()), "hsl(16.1 100% 65.7%)");
+        assert_eq!(Color::WHITE.to_css_oklch().to_string(), "oklch(1 0 0)");
+        assert_eq!(Color::BLACK.to_css_oklch().to_string(), "oklch(0 0 0)");
+    }
+}
@@ -0,0 +1,183 @@
+#![allow(dead_code)]
+
+// Color vision deficiency simulation, for checking that palettes survive colorblind viewers.
+// the dichromacies use the Machado, Oliveira & Fernandes (2009) matrices at full severity,
+// applied to linear sRGB. partial severities blend the matrix with the identity, which is
+// a close approximation of the paper's per-severity tables.
+//
+// source: https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html
+
+use crate::color::{ColorFloat, model::Color};
+
+type Mat3 = [[ColorFloat; 3]; 3];
+
+const PROTANOPIA: Mat3 = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+const DEUTERANOPIA: Mat3 = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+const TRITANOPIA: Mat3 = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+/// Every channel becomes the Rec. 709 luminance.
+const ACHROMATOPSIA: Mat3 = [
+    [0.2126, 0.7152, 0.0722],
+    [0.2126, 0.7152, 0.0722],
+    [0.2126, 0.7152, 0.0722],
+];
+
+/// An enum naming the simulated color vision deficiencies.
+///
+/// # Variants
+///
+/// - `Protanopia` - No working long-wavelength (red) cones. Reds look dark and
+///   are confused with greens.
+///
+/// - `Deuteranopia` - No working medium-wavelength (green) cones. The most common
+///   form of red-green colorblindness.
+///
+/// - `Tritanopia` - No working short-wavelength (blue) cones. Blues are confused
+///   with greens and yellows with violets.
+///
+/// - `Achromatopsia` - No working cones at all. Only lightness is seen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorVisionDeficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    Achromatopsia,
+}
+
+impl ColorVisionDeficiency {
+    /// Every deficiency, in declaration order.
+    pub const ALL: [ColorVisionDeficiency; 4] = [
+        ColorVisionDeficiency::Protanopia,
+        ColorVisionDeficiency::Deuteranopia,
+        ColorVisionDeficiency::Tritanopia,
+        ColorVisionDeficiency::Achromatopsia,
+    ];
+
+    /// The linear sRGB simulation matrix at a severity.
+    ///
+    /// `severity` is clamped to 0.0-1.0, where 0.0 is normal vision.
+    #[must_use]
+    pub(crate) fn matrix(self, severity: ColorFloat) -> Mat3 {
+        use ColorVisionDeficiency::*;
+
+        let full = match self {
+            Protanopia => PROTANOPIA,
+            Deuteranopia => DEUTERANOPIA,
+            Tritanopia => TRITANOPIA,
+            Achromatopsia => ACHROMATOPSIA,
+        };
+        let s = severity.clamp(0.0, 1.0);
+
+        let mut m = full;
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                let identity = if i == j { 1.0 } else { 0.0 };
+                *v = identity + (*v - identity) * s;
+            }
+        }
+        m
+    }
+}
+
+impl Color {
+    /// Simulate how a color looks to someone with a color vision deficiency.
+    ///
+    /// The simulation runs in linear sRGB. Alpha is kept.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to simulate.
+    /// - `deficiency` (`ColorVisionDeficiency`) - The deficiency to simulate.
+    /// - `severity` (`ColorFloat`) - How strong the deficiency is, from 0.0 (normal
+    ///   vision) to 1.0 (full dichromacy). This value will be clamped between and
+    ///   including 0.0 and 1.0.
+    ///
+    /// # Returns
+    ///
+    /// - `Color` - The color as it would be seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::{Color, cvd::ColorVisionDeficiency};
+    ///
+    /// let crimson = Color::new(220, 20, 60, 255);
+    /// let seen = crimson.simulate_cvd(ColorVisionDeficiency::Deuteranopia, 1.0);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn simulate_cvd(self, deficiency: ColorVisionDeficiency, severity: ColorFloat) -> Color {
+        self.transform_linear(&deficiency.matrix(severity))
+    }
+
+    /// Multiply the linear sRGB channels by a matrix, keeping alpha.
+    #[must_use]
+    #[inline]
+    pub(crate) fn transform_linear(self, m: &Mat3) -> Color {
+        let [r, g, b, _] = self.into_linear();
+        let row = |i: usize| m[i][0] * r + m[i][1] * g + m[i][2] * b;
+        Color::from_linear([row(0), row(1), row(2), 1.0]).with_alpha(self.into_rgba()[3])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_sum_to_one() {
+        for deficiency in ColorVisionDeficiency::ALL {
+            for row in deficiency.matrix(1.0) {
+                let sum: ColorFloat = row.iter().sum();
+                assert!((sum - 1.0).abs() < 1e-5, "{deficiency:?} {row:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn white_and_black_are_fixed() {
+        for deficiency in ColorVisionDeficiency::ALL {
+            for severity in [0.25, 0.5, 1.0] {
+                for c in [Color::WHITE, Color::BLACK, Color::new(255, 255, 255, 128)] {
+                    assert_eq!(c.simulate_cvd(deficiency, severity), c, "{deficiency:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn zero_severity_is_the_identity() {
+        let mut seed = 0x2545_f491_u32;
+        for _ in 0..1000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            let [r, g, b, a] = seed.to_le_bytes();
+            let c = Color::new(r, g, b, a);
+            for deficiency in ColorVisionDeficiency::ALL {
+                assert_eq!(c.simulate_cvd(deficiency, 0.0), c, "{deficiency:?}");
+                // severities below zero clamp to normal vision
+                assert_eq!(c.simulate_cvd(deficiency, -1.0), c, "{deficiency:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn achromatopsia_is_gray() {
+        let [r, g, b, a] = Color::new(220, 20, 60, 200)
+            .simulate_cvd(ColorVisionDeficiency::Achromatopsia, 1.0)
+            .into_rgba();
+        assert_eq!((r, r, a), (g, b, 200));
+    }
+}
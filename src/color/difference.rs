@@ -0,0 +1,256 @@
+#![allow(dead_code)]
+
+// Perceptual color differences. CIEDE2000 is the industry standard and what most
+// tolerances are quoted in; deltaEOK is the plain distance in OKLAB that CSS Color 4
+// uses for gamut mapping, and is much cheaper.
+
+#[cfg(not(feature = "std"))]
+use crate::color::math::FloatMath;
+use crate::color::{ColorFloat, gamut, model::Color};
+
+impl Color {
+    /// Calculate the CIEDE2000 color difference between two colors.
+    ///
+    /// Uses CIELAB with a D50 white point (as `Color::into_lab` does) and the
+    /// default weights `kL = kC = kH = 1`. A difference around 1.0 is just
+    /// noticeable; 2.3 is a common "same color" tolerance. Alpha is ignored.
+    ///
+    /// Source: http://www2.ece.rochester.edu/~gsharma/ciede2000/ciede2000noteCRNA.pdf
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The first color.
+    /// - `other` (`Color`) - The second color.
+    ///
+    /// # Returns
+    ///
+    /// - `ColorFloat` - The difference, 0.0 for identical colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let navy = Color::new(0, 0, 128, 255);
+    /// let dark_blue = Color::new(0, 0, 139, 255);
+    /// let close = navy.delta_e_2000(dark_blue) < 2.3;
+    /// ```
+    #[must_use]
+    pub fn delta_e_2000(self, other: Color) -> ColorFloat {
+        ciede2000(self.into_lab(), other.into_lab())
+    }
+
+    /// Calculate the OKLAB color difference (deltaEOK) between two colors.
+    ///
+    /// This is the Euclidean distance in OKLAB. Since OKLAB lightness runs
+    /// from 0.0 to 1.0, a difference around 0.02 is just noticeable.
+    /// Alpha is ignored.
+    ///
+    /// Source: https://www.w3.org/TR/css-color-4/#color-difference-OK
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The first color.
+    /// - `other` (`Color`) - The second color.
+    ///
+    /// # Returns
+    ///
+    /// - `ColorFloat` - The difference, 0.0 for identical colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let gold = Color::new(255, 215, 0, 255);
+    /// let goldenrod = Color::new(218, 165, 32, 255);
+    /// let diff = gold.delta_e_ok(goldenrod);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn delta_e_ok(self, other: Color) -> ColorFloat {
+        gamut::delta_e_ok(self.into_oklab(), other.into_oklab())
+    }
+}
+
+/// CIEDE2000 between two CIELAB arrays.
+pub(crate) fn ciede2000(lab1: [ColorFloat; 3], lab2: [ColorFloat; 3]) -> ColorFloat {
+    // 25^7, used by the chroma compensation terms
+    const POW25_7: ColorFloat = 6_103_515_625.0;
+
+    let [l1, a1, b1] = lab1;
+    let [l2, a2, b2] = lab2;
+
+    // a' stretches the a axis for low-chroma (near neutral) colors
+    let c_bar = ((a1 * a1 + b1 * b1).sqrt() + (a2 * a2 + b2 * b2).sqrt()) * 0.5;
+    let c_bar7 = c_bar.powi(7);
+    let g = 0.5 * (1.0 - (c_bar7 / (c_bar7 + POW25_7)).sqrt());
+    let a1p = a1 * (1.0 + g);
+    let a2p = a2 * (1.0 + g);
+
+    let c1p = (a1p * a1p + b1 * b1).sqrt();
+    let c2p = (a2p * a2p + b2 * b2).sqrt();
+    let hue = |b: ColorFloat, ap: ColorFloat| {
+        if b == 0.0 && ap == 0.0 {
+            0.0
+        } else {
+            b.atan2(ap).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1p = hue(b1, a1p);
+    let h2p = hue(b2, a2p);
+
+    // differences
+    let dl = l2 - l1;
+    let dc = c2p - c1p;
+    let dh = if c1p * c2p == 0.0 {
+        0.0
+    } else {
+        let d = h2p - h1p;
+        if d > 180.0 {
+            d - 360.0
+        } else if d < -180.0 {
+            d + 360.0
+        } else {
+            d
+        }
+    };
+    let dh_big = 2.0 * (c1p * c2p).sqrt() * (dh.to_radians() * 0.5).sin();
+
+    // means
+    let l_bar = (l1 + l2) * 0.5;
+    let c_bar_p = (c1p + c2p) * 0.5;
+    let h_bar_p = if c1p * c2p == 0.0 {
+        h1p + h2p
+    } else if (h1p - h2p).abs() <= 180.0 {
+        (h1p + h2p) * 0.5
+    } else if h1p + h2p < 360.0 {
+        (h1p + h2p + 360.0) * 0.5
+    } else {
+        (h1p + h2p - 360.0) * 0.5
+    };
+
+    // weighting functions
+    let t = 1.0 - 0.17 * (h_bar_p - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar_p).to_radians().cos()
+        + 0.32 * (3.0 * h_bar_p + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar_p - 63.0).to_radians().cos();
+    let l50 = (l_bar - 50.0) * (l_bar - 50.0);
+    let sl = 1.0 + 0.015 * l50 / (20.0 + l50).sqrt();
+    let sc = 1.0 + 0.045 * c_bar_p;
+    let sh = 1.0 + 0.015 * c_bar_p * t;
+
+    // rotation term for the blue region
+    let d_theta = 30.0 * (-((h_bar_p - 275.0) / 25.0).powi(2)).exp();
+    let c_bar_p7 = c_bar_p.powi(7);
+    let rc = 2.0 * (c_bar_p7 / (c_bar_p7 + POW25_7)).sqrt();
+    let rt = -(2.0 * d_theta).to_radians().sin() * rc;
+
+    let (tl, tc, th) = (dl / sl, dc / sc, dh_big / sh);
+    (tl * tl + tc * tc + th * th + rt * tc * th).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sharma, Wu & Dalal test data: [L1, a1, b1, L2, a2, b2, dE00]
+    // source: http://www2.ece.rochester.edu/~gsharma/ciede2000/dataNprograms/ciede2000testdata.txt
+    #[rustfmt::skip]
+    const SHARMA: [[ColorFloat; 7]; 34] = [
+        [50.0000,   2.6772, -79.7751, 50.0000,   0.0000, -82.7485,  2.0425],
+        [50.0000,   3.1571, -77.2803, 50.0000,   0.0000, -82.7485,  2.8615],
+        [50.0000,   2.8361, -74.0200, 50.0000,   0.0000, -82.7485,  3.4412],
+        [50.0000,  -1.3802, -84.2814, 50.0000,   0.0000, -82.7485,  1.0000],
+        [50.0000,  -1.1848, -84.8006, 50.0000,   0.0000, -82.7485,  1.0000],
+        [50.0000,  -0.9009, -85.5211, 50.0000,   0.0000, -82.7485,  1.0000],
+        [50.0000,   0.0000,   0.0000, 50.0000,  -1.0000,   2.0000,  2.3669],
+        [50.0000,  -1.0000,   2.0000, 50.0000,   0.0000,   0.0000,  2.3669],
+        [50.0000,   2.4900,  -0.0010, 50.0000,  -2.4900,   0.0009,  7.1792],
+        [50.0000,   2.4900,  -0.0010, 50.0000,  -2.4900,   0.0010,  7.1792],
+        [50.0000,   2.4900,  -0.0010, 50.0000,  -2.4900,   0.0011,  7.2195],
+        [50.0000,   2.4900,  -0.0010, 50.0000,  -2.4900,   0.0012,  7.2195],
+        [50.0000,  -0.0010,   2.4900, 50.0000,   0.0009,  -2.4900,  4.8045],
+        [50.0000,  -0.0010,   2.4900, 50.0000,   0.0010,  -2.4900,  4.8045],
+        [50.0000,  -0.0010,   2.4900, 50.0000,   0.0011,  -2.4900,  4.7461],
+        [50.0000,   2.5000,   0.0000, 50.0000,   0.0000,  -2.5000,  4.3065],
+        [50.0000,   2.5000,   0.0000, 73.0000,  25.0000, -18.0000, 27.1492],
+        [50.0000,   2.5000,   0.0000, 61.0000,  -5.0000,  29.0000, 22.8977],
+        [50.0000,   2.5000,   0.0000, 56.0000, -27.0000,  -3.0000, 31.9030],
+        [50.0000,   2.5000,   0.0000, 58.0000,  24.0000,  15.0000, 19.4535],
+        [50.0000,   2.5000,   0.0000, 50.0000,   3.1736,   0.5854,  1.0000],
+        [50.0000,   2.5000,   0.0000, 50.0000,   3.2972,   0.0000,  1.0000],
+        [50.0000,   2.5000,   0.0000, 50.0000,   1.8634,   0.5757,  1.0000],
+        [50.0000,   2.5000,   0.0000, 50.0000,   3.2592,   0.3350,  1.0000],
+        [60.2574, -34.0099,  36.2677, 60.4626, -34.1751,  39.4387,  1.2644],
+        [63.0109, -31.0961,  -5.8663, 62.8187, -29.7946,  -4.0864,  1.2630],
+        [61.2901,   3.7196,  -5.3901, 61.4292,   2.2480,  -4.9620,  1.8731],
+        [35.0831, -44.1164,   3.7933, 35.0232, -40.0716,   1.5901,  1.8645],
+        [22.7233,  20.0904, -46.6940, 23.0331,  14.9730, -42.5619,  2.0373],
+        [36.4612,  47.8580,  18.3852, 36.2715,  50.5065,  21.2231,  1.4146],
+        [90.8027,  -2.0831,   1.4410, 91.1528,  -1.6435,   0.0447,  1.4441],
+        [90.9257,  -0.5406,  -0.9208, 88.6381,  -0.8985,  -0.7239,  1.5381],
+        [ 6.7747,  -0.2908,  -2.4247,  5.8714,  -0.0985,  -2.2286,  0.6377],
+        [ 2.0776,   0.0795,  -1.1350,  0.9033,  -0.0636,  -0.5514,  0.9082],
+    ];
+
+    const SAMPLES: [Color; 6] = [
+        Color::BLACK,
+        Color::WHITE,
+        Color::new(128, 128, 128, 255),
+        Color::new(0, 0, 128, 255),
+        Color::new(255, 215, 0, 255),
+        Color::new(34, 139, 34, 255),
+    ];
+
+    #[test]
+    fn ciede2000_matches_sharma_data() {
+        for (i, [l1, a1, b1, l2, a2, b2, expected]) in SHARMA.into_iter().enumerate() {
+            let lab1 = [l1, a1, b1];
+            let lab2 = [l2, a2, b2];
+            let de = ciede2000(lab1, lab2);
+            assert!(
+                (de - expected).abs() < 1e-4,
+                "pair {}: {de} != {expected}",
+                i + 1
+            );
+            // the formula is symmetric, including the hue wrap and mean-hue cases
+            let de = ciede2000(lab2, lab1);
+            assert!((de - expected).abs() < 1e-4, "pair {} swapped: {de}", i + 1);
+        }
+    }
+
+    #[test]
+    fn delta_e_is_zero_for_identical_colors() {
+        for c in SAMPLES {
+            assert_eq!(c.delta_e_2000(c), 0.0);
+            assert_eq!(c.delta_e_ok(c), 0.0);
+        }
+    }
+
+    #[test]
+    fn delta_e_is_symmetric() {
+        for a in SAMPLES {
+            for b in SAMPLES {
+                assert!((a.delta_e_2000(b) - b.delta_e_2000(a)).abs() < 1e-4);
+                assert_eq!(a.delta_e_ok(b), b.delta_e_ok(a));
+                if a != b {
+                    assert!(a.delta_e_2000(b) > 0.0);
+                    assert!(a.delta_e_ok(b) > 0.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn delta_e_2000_uses_d50_lab() {
+        let navy = Color::new(0, 0, 128, 255);
+        let gold = Color::new(255, 215, 0, 255);
+        assert_eq!(
+            navy.delta_e_2000(gold),
+            ciede2000(navy.into_lab(), gold.into_lab())
+        );
+        // black against white spans the whole lightness axis
+        assert!((Color::BLACK.delta_e_2000(Color::WHITE) - 100.0).abs() < 0.01);
+    }
+}
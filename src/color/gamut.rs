@@ -0,0 +1,500 @@
+#![allow(dead_code)]
+
+// Wide-gamut color spaces (CIE XYZ, Display P3, Rec. 2020) and CSS Color 4 gamut mapping.
+// `Color` is always sRGB, so anything coming in from a wider space gets gamut mapped
+// on the way in instead of being clipped channel by channel (which shifts the hue).
+//
+// matrices and the mapping algorithm come from https://www.w3.org/TR/css-color-4/
+
+#[cfg(not(feature = "std"))]
+use crate::color::math::FloatMath;
+use crate::color::{ColorFloat, model::Color};
+
+pub(crate) type Mat3 = [[f64; 3]; 3];
+
+#[rustfmt::skip]
+pub(crate) const LINEAR_SRGB_TO_XYZ_D65: Mat3 = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+#[rustfmt::skip]
+pub(crate) const XYZ_D65_TO_LINEAR_SRGB: Mat3 = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [0.05563007969699366, -0.20397695888897652, 1.0569715142428786],
+];
+#[rustfmt::skip]
+const LINEAR_P3_TO_XYZ_D65: Mat3 = [
+    [0.4865709486482162, 0.26566769316909306, 0.1982172852343625],
+    [0.2289745640697488, 0.6917385218365064, 0.079286914093745],
+    [0.0, 0.04511338185890264, 1.043944368900976],
+];
+#[rustfmt::skip]
+const XYZ_D65_TO_LINEAR_P3: Mat3 = [
+    [2.493496911941425, -0.9313836179191239, -0.40271078445071684],
+    [-0.8294889695615747, 1.7626640603183463, 0.023624685841943577],
+    [0.03584583024378447, -0.07617238926804182, 0.9568845240076872],
+];
+#[rustfmt::skip]
+const LINEAR_REC2020_TO_XYZ_D65: Mat3 = [
+    [0.6369580483012914, 0.14461690358620832, 0.1688809751641721],
+    [0.2627002120112671, 0.6779980715188708, 0.05930171646986196],
+    [0.0, 0.028072693049087428, 1.060985057710791],
+];
+#[rustfmt::skip]
+const XYZ_D65_TO_LINEAR_REC2020: Mat3 = [
+    [1.7166511879712674, -0.35567078377639233, -0.25336628137365974],
+    [-0.6666843518324892, 1.6164812366349395, 0.01576854581391113],
+    [0.017639857445310783, -0.042770613257808524, 0.9421031212354738],
+];
+/// Bradford chromatic adaptation.
+#[rustfmt::skip]
+const D65_TO_D50: Mat3 = [
+    [1.0479297925449969, 0.022946870601609652, -0.05019226628920524],
+    [0.02962780877005599, 0.9904344267538799, -0.017073799063418826],
+    [-0.009243040646204504, 0.015055191490298152, 0.7518742814281371],
+];
+/// Bradford chromatic adaptation.
+#[rustfmt::skip]
+const D50_TO_D65: Mat3 = [
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [-0.0283697093338637, 1.0099953980813041, 0.021041441191917323],
+    [0.012314014864481998, -0.020507649298898964, 1.330365926242124],
+];
+
+/// Just noticeable difference in OKLAB, used by the gamut mapping.
+const JND: ColorFloat = 0.02;
+/// Chroma precision of the gamut mapping binary search.
+const MAP_EPSILON: ColorFloat = 0.0001;
+
+// ColorFloat may already be f64
+#[allow(clippy::unnecessary_cast)]
+#[inline]
+pub(crate) fn mul3(m: &Mat3, v: [ColorFloat; 3]) -> [ColorFloat; 3] {
+    let [x, y, z] = v.map(|c| c as f64);
+    m.map(|row| (row[0] * x + row[1] * y + row[2] * z) as ColorFloat)
+}
+
+/// The matrix product `a * b`.
+#[inline]
+pub(crate) fn mat_mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    core::array::from_fn(|i| core::array::from_fn(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+/// The sRGB (and Display P3) transfer function, extended to negative values.
+#[inline]
+fn srgb_encode(lin: ColorFloat) -> ColorFloat {
+    let abs = lin.abs();
+    let v = if abs <= 0.0031308 {
+        12.92 * abs
+    } else {
+        1.055 * abs.powf(1.0 / 2.4) - 0.055
+    };
+    v.copysign(lin)
+}
+
+/// The inverse sRGB (and Display P3) transfer function, extended to negative values.
+#[inline]
+fn srgb_decode(v: ColorFloat) -> ColorFloat {
+    let abs = v.abs();
+    let lin = if abs <= 0.04045 {
+        abs / 12.92
+    } else {
+        ((abs + 0.055) / 1.055).powf(2.4)
+    };
+    lin.copysign(v)
+}
+
+// Rec. 2020 transfer constants
+const REC2020_ALPHA: ColorFloat = 1.099_296_8;
+const REC2020_BETA: ColorFloat = 0.018_053_97;
+
+#[inline]
+fn rec2020_encode(lin: ColorFloat) -> ColorFloat {
+    let abs = lin.abs();
+    let v = if abs < REC2020_BETA {
+        4.5 * abs
+    } else {
+        REC2020_ALPHA * abs.powf(0.45) - (REC2020_ALPHA - 1.0)
+    };
+    v.copysign(lin)
+}
+
+#[inline]
+fn rec2020_decode(v: ColorFloat) -> ColorFloat {
+    let abs = v.abs();
+    let lin = if abs < REC2020_BETA * 4.5 {
+        abs / 4.5
+    } else {
+        ((abs + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+    };
+    lin.copysign(v)
+}
+
+/// Whether a linear sRGB triple is inside the sRGB gamut.
+#[inline]
+pub(crate) fn in_srgb_gamut(lin: [ColorFloat; 3]) -> bool {
+    lin.iter().all(|c| (0.0..=1.0).contains(c))
+}
+
+/// Euclidean distance in OKLAB (deltaEOK).
+#[inline]
+pub(crate) fn delta_e_ok(a: [ColorFloat; 3], b: [ColorFloat; 3]) -> ColorFloat {
+    let (dl, da, db) = (a[0] - b[0], a[1] - b[1], a[2] - b[2]);
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+/// Map an OKLCH color into the sRGB gamut, returning linear sRGB in 0.0..=1.0.
+///
+/// This is the CSS Color 4 gamut mapping algorithm: chroma is binary searched
+/// at a fixed lightness and hue until clipping the result is within a just
+/// noticeable difference of the unclipped color.
+/// Source: https://www.w3.org/TR/css-color-4/#binsearch
+pub(crate) fn gamut_map_oklch(lch: [ColorFloat; 3]) -> [ColorFloat; 3] {
+    let [l, c, h] = lch;
+    if l >= 1.0 {
+        return [1.0; 3];
+    }
+    if l <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let to_linear = |c: ColorFloat| Color::oklab_to_linear(Color::oklch_to_oklab([l, c, h]));
+    let clip = |lin: [ColorFloat; 3]| lin.map(|v| v.clamp(0.0, 1.0));
+    // distance between a color and its clipped version, in OKLAB
+    let clip_error = |c: ColorFloat, clipped: [ColorFloat; 3]| {
+        delta_e_ok(
+            Color::oklch_to_oklab([l, c, h]),
+            Color::linear_to_oklab(clipped),
+        )
+    };
+
+    let origin = to_linear(c);
+    if in_srgb_gamut(origin) {
+        return origin;
+    }
+
+    let mut clipped = clip(origin);
+    if clip_error(c, clipped) < JND {
+        return clipped;
+    }
+
+    let (mut min, mut max) = (0.0, c);
+    let mut min_in_gamut = true;
+    while max - min > MAP_EPSILON {
+        let chroma = (min + max) / 2.0;
+        let current = to_linear(chroma);
+
+        if min_in_gamut && in_srgb_gamut(current) {
+            min = chroma;
+            continue;
+        }
+
+        clipped = clip(current);
+        let e = clip_error(chroma, clipped);
+        if e < JND {
+            if JND - e < MAP_EPSILON {
+                return clipped;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    clipped
+}
+
+/// Gamut map an unclamped linear sRGB triple into 0.0..=1.0.
+#[inline]
+fn map_linear_srgb(lin: [ColorFloat; 3]) -> [ColorFloat; 3] {
+    if in_srgb_gamut(lin) {
+        lin
+    } else {
+        gamut_map_oklch(Color::oklab_to_oklch(Color::linear_to_oklab(lin)))
+    }
+}
+
+impl Color {
+    /// Create a color from CIE XYZ, relative to the D65 white point.
+    ///
+    /// Colors outside of the sRGB gamut are gamut mapped like `Color::from_oklch`.
+    ///
+    /// # Arguments
+    ///
+    /// - `xyz` (`[ColorFloat; 3]`) - The XYZ array (Y = 1.0 for white).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let white = Color::from_xyz_d65([0.95047, 1.0, 1.08883]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_xyz_d65(xyz: [ColorFloat; 3]) -> Self {
+        let [r, g, b] = map_linear_srgb(mul3(&XYZ_D65_TO_LINEAR_SRGB, xyz));
+        Self::from_linear([r, g, b, 1.0])
+    }
+
+    /// Get a CIE XYZ representation of a color, relative to the D65 white point.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The XYZ representation (Y = 1.0 for white).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let [x, y, z] = Color::new(255, 99, 71, 255).into_xyz_d65();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_xyz_d65(self) -> [ColorFloat; 3] {
+        let [r, g, b, _] = self.into_linear();
+        mul3(&LINEAR_SRGB_TO_XYZ_D65, [r, g, b])
+    }
+
+    /// Create a color from CIE XYZ, relative to the D50 white point.
+    ///
+    /// The white point is adapted to D65 with the Bradford transform. Colors outside
+    /// of the sRGB gamut are gamut mapped like `Color::from_oklch`.
+    ///
+    /// # Arguments
+    ///
+    /// - `xyz` (`[ColorFloat; 3]`) - The XYZ array (Y = 1.0 for white).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let white = Color::from_xyz_d50([0.96422, 1.0, 0.82521]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_xyz_d50(xyz: [ColorFloat; 3]) -> Self {
+        Self::from_xyz_d65(mul3(&D50_TO_D65, xyz))
+    }
+
+    /// Get a CIE XYZ representation of a color, relative to the D50 white point.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The XYZ representation (Y = 1.0 for white).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let [x, y, z] = Color::new(255, 99, 71, 255).into_xyz_d50();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_xyz_d50(self) -> [ColorFloat; 3] {
+        mul3(&D65_TO_D50, self.into_xyz_d65())
+    }
+
+    /// Create a color from gamma-encoded Display P3 values.
+    ///
+    /// Colors outside of the sRGB gamut are gamut mapped like `Color::from_oklch`.
+    ///
+    /// # Arguments
+    ///
+    /// - `rgb` (`[ColorFloat; 3]`) - The Display P3 channels in 0.0-1.0.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let p3_red = Color::from_display_p3([1.0, 0.0, 0.0]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_display_p3(rgb: [ColorFloat; 3]) -> Self {
+        let lin = rgb.map(srgb_decode);
+        Self::from_xyz_d65(mul3(&LINEAR_P3_TO_XYZ_D65, lin))
+    }
+
+    /// Get a gamma-encoded Display P3 representation of a color.
+    ///
+    /// sRGB fits inside Display P3, so this never needs gamut mapping.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The Display P3 channels in 0.0-1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let [r, g, b] = Color::new(255, 0, 0, 255).into_display_p3();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_display_p3(self) -> [ColorFloat; 3] {
+        mul3(&XYZ_D65_TO_LINEAR_P3, self.into_xyz_d65()).map(srgb_encode)
+    }
+
+    /// Create a color from gamma-encoded Rec. 2020 values.
+    ///
+    /// Colors outside of the sRGB gamut are gamut mapped like `Color::from_oklch`.
+    ///
+    /// # Arguments
+    ///
+    /// - `rgb` (`[ColorFloat; 3]`) - The Rec. 2020 channels in 0.0-1.0.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let rec2020_green = Color::from_rec2020([0.0, 1.0, 0.0]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_rec2020(rgb: [ColorFloat; 3]) -> Self {
+        let lin = rgb.map(rec2020_decode);
+        Self::from_xyz_d65(mul3(&LINEAR_REC2020_TO_XYZ_D65, lin))
+    }
+
+    /// Get a gamma-encoded Rec. 2020 representation of a color.
+    ///
+    /// sRGB fits inside Rec. 2020, so this never needs gamut mapping.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The Rec. 2020 channels in 0.0-1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let [r, g, b] = Color::new(0, 255, 0, 255).into_rec2020();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_rec2020(self) -> [ColorFloat; 3] {
+        mul3(&XYZ_D65_TO_LINEAR_REC2020, self.into_xyz_d65()).map(rec2020_encode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every 17th value per channel, which includes 0 and 255.
+    fn grid() -> impl Iterator<Item = Color> {
+        (0..=255u8).step_by(17).flat_map(|r| {
+            (0..=255u8).step_by(17).flat_map(move |g| {
+                (0..=255u8)
+                    .step_by(17)
+                    .map(move |b| Color::new(r, g, b, 255))
+            })
+        })
+    }
+
+    fn hue_distance(a: ColorFloat, b: ColorFloat) -> ColorFloat {
+        let d = (a - b).rem_euclid(360.0);
+        d.min(360.0 - d)
+    }
+
+    #[test]
+    fn srgb_round_trips_through_wide_gamuts() {
+        for c in grid() {
+            assert_eq!(Color::from_display_p3(c.into_display_p3()), c, "p3");
+            assert_eq!(Color::from_rec2020(c.into_rec2020()), c, "rec2020");
+            assert_eq!(Color::from_xyz_d65(c.into_xyz_d65()), c, "xyz d65");
+            assert_eq!(Color::from_xyz_d50(c.into_xyz_d50()), c, "xyz d50");
+        }
+    }
+
+    #[test]
+    fn wide_gamut_primaries_are_outside_srgb() {
+        let [r, g, b] = Color::new(255, 0, 0, 255).into_display_p3();
+        assert!(r < 0.95 && g > 0.0 && b > 0.0, "{r} {g} {b}");
+        // the P3 red is gamut mapped back to an sRGB red, not clipped to a different hue
+        let red = Color::from_display_p3([1.0, 0.0, 0.0]).into_rgba();
+        assert_eq!((red[0], red[3]), (255, 255));
+        assert!(red[1] < 40 && red[2] < 40, "{red:?}");
+    }
+
+    #[test]
+    fn in_gamut_oklch_is_unchanged() {
+        for c in grid() {
+            let lch = c.into_oklch();
+            let lin = Color::oklab_to_linear(Color::oklch_to_oklab(lch));
+            if in_srgb_gamut(lin) {
+                assert_eq!(gamut_map_oklch(lch), lin, "{c:?}");
+            }
+            assert_eq!(Color::from_oklch(lch), c);
+        }
+    }
+
+    #[test]
+    fn gamut_mapping_keeps_hue_where_clipping_does_not() {
+        let lch = [0.7, 0.4, 150.0];
+        let lin = Color::oklab_to_linear(Color::oklch_to_oklab(lch));
+        assert!(!in_srgb_gamut(lin));
+
+        let mapped = gamut_map_oklch(lch);
+        assert!(in_srgb_gamut(mapped));
+        let [l, c, h] = Color::oklab_to_oklch(Color::linear_to_oklab(mapped));
+        // the last step clips within a just noticeable difference, which can move the hue a little
+        assert!(hue_distance(h, 150.0) < 4.0, "mapped hue {h}");
+        assert!((l - 0.7).abs() < 0.02, "mapped lightness {l}");
+        assert!(c < 0.4);
+
+        let clipped = lin.map(|v| v.clamp(0.0, 1.0));
+        let [l, _, h] = Color::oklab_to_oklch(Color::linear_to_oklab(clipped));
+        assert!(hue_distance(h, 150.0) > 6.0, "clipped hue {h}");
+        assert!((l - 0.7).abs() > 0.05, "clipped lightness {l}");
+    }
+
+    #[test]
+    fn oklch_lightness_extremes() {
+        assert_eq!(gamut_map_oklch([1.0, 0.3, 40.0]), [1.0; 3]);
+        assert_eq!(gamut_map_oklch([0.0, 0.3, 40.0]), [0.0; 3]);
+    }
+}
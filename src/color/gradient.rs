@@ -0,0 +1,472 @@
+#![allow(dead_code)]
+
+// Multi-stop gradients. interpolation follows CSS Color 4 (premultiplied alpha,
+// "missing" hues for grays, and the four hue interpolation methods) so a gradient
+// here looks the same as the equivalent `linear-gradient(in oklch ...)` in a browser.
+// stops live in a Vec, so this module needs the `alloc` feature.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use crate::color::math::FloatMath;
+use crate::color::{ColorFloat, model::Color};
+
+/// The color space a `Gradient` interpolates in.
+///
+/// # Variants
+///
+/// - `Srgb` - Gamma-encoded sRGB. Matches what most design tools do, but
+///   midpoints between saturated colors tend to look muddy.
+///
+/// - `Linear` - Linear-light sRGB. Physically correct mixing of light.
+///
+/// - `Oklab` - OKLAB. Perceptually even lightness steps without hue drift.
+///
+/// - `Oklch` - OKLCH. Like `Oklab`, but the hue travels around the color wheel
+///   (see `HueInterpolation`), which keeps midpoints saturated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InterpolationSpace {
+    Srgb,
+    Linear,
+    #[default]
+    Oklab,
+    Oklch,
+}
+
+/// Which way hues travel around the color wheel when interpolating in OKLCH.
+///
+/// These match the CSS Color 4 hue interpolation methods:
+/// https://www.w3.org/TR/css-color-4/#hue-interpolation
+///
+/// # Variants
+///
+/// - `Shorter` - Take the shorter arc (at most 180 degrees).
+///
+/// - `Longer` - Take the longer arc (at least 180 degrees).
+///
+/// - `Increasing` - Always move towards larger hue angles.
+///
+/// - `Decreasing` - Always move towards smaller hue angles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HueInterpolation {
+    #[default]
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+/// A color at a position along a gradient.
+///
+/// # Fields
+///
+/// - `color` (`Color`) - The color at this stop.
+/// - `position` (`ColorFloat`) - Where the stop sits, usually in 0.0-1.0.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorStop {
+    pub color: Color,
+    pub position: ColorFloat,
+}
+
+impl ColorStop {
+    /// Create a new color stop.
+    #[must_use]
+    #[inline]
+    pub const fn new(color: Color, position: ColorFloat) -> Self {
+        Self { color, position }
+    }
+}
+
+/// A multi-stop color gradient.
+///
+/// Colors are interpolated with premultiplied alpha (as CSS does), so fading
+/// to a transparent stop doesn't pull in the transparent stop's color.
+///
+/// # Examples
+///
+/// ```
+/// use codimate::color::{Color, gradient::{Gradient, InterpolationSpace}};
+///
+/// let ramp = Gradient::new([Color::BLUE, Color::WHITE, Color::RED])
+///     .with_space(InterpolationSpace::Oklch);
+/// let middle = ramp.at(0.5);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Gradient {
+    stops: Vec<ColorStop>,
+    space: InterpolationSpace,
+    hue: HueInterpolation,
+}
+
+impl Gradient {
+    /// Create a gradient with the given colors spread evenly from 0.0 to 1.0.
+    ///
+    /// # Arguments
+    ///
+    /// - `colors` (`impl IntoIterator<Item = Color>`) - The stop colors, in order.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new gradient, interpolating in OKLAB.
+    #[must_use]
+    pub fn new(colors: impl IntoIterator<Item = Color>) -> Self {
+        let colors: Vec<Color> = colors.into_iter().collect();
+        let last = colors.len().saturating_sub(1).max(1) as ColorFloat;
+        let stops = colors
+            .into_iter()
+            .enumerate()
+            .map(|(i, color)| ColorStop::new(color, i as ColorFloat / last))
+            .collect();
+
+        Self {
+            stops,
+            ..Self::default()
+        }
+    }
+
+    /// Create a gradient from explicitly positioned stops.
+    ///
+    /// Stops are sorted by position. Stops sharing a position make a hard edge.
+    ///
+    /// # Arguments
+    ///
+    /// - `stops` (`impl IntoIterator<Item = ColorStop>`) - The stops.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new gradient, interpolating in OKLAB.
+    #[must_use]
+    pub fn from_stops(stops: impl IntoIterator<Item = ColorStop>) -> Self {
+        let mut stops: Vec<ColorStop> = stops.into_iter().collect();
+        // stable, so equal positions keep their given order
+        stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+
+        Self {
+            stops,
+            ..Self::default()
+        }
+    }
+
+    /// Copy a gradient but with a different interpolation space.
+    #[must_use]
+    #[inline]
+    pub fn with_space(mut self, space: InterpolationSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Copy a gradient but with a different hue interpolation method.
+    ///
+    /// This only matters when interpolating in `InterpolationSpace::Oklch`.
+    #[must_use]
+    #[inline]
+    pub fn with_hue_interpolation(mut self, hue: HueInterpolation) -> Self {
+        self.hue = hue;
+        self
+    }
+
+    /// Add a stop, keeping the stops sorted.
+    ///
+    /// A stop at the same position as existing ones goes after them.
+    pub fn add_stop(&mut self, color: Color, position: ColorFloat) {
+        let i = self.stops.partition_point(|s| s.position <= position);
+        self.stops.insert(i, ColorStop::new(color, position));
+    }
+
+    /// The stops, sorted by position.
+    #[must_use]
+    #[inline]
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+
+    /// The interpolation space.
+    #[must_use]
+    #[inline]
+    pub fn space(&self) -> InterpolationSpace {
+        self.space
+    }
+
+    /// The hue interpolation method.
+    #[must_use]
+    #[inline]
+    pub fn hue_interpolation(&self) -> HueInterpolation {
+        self.hue
+    }
+
+    /// Sample the gradient.
+    ///
+    /// Positions before the first stop or after the last stop take that stop's color.
+    /// An empty gradient is `Color::TRANSPARENT`.
+    ///
+    /// # Arguments
+    ///
+    /// - `t` (`ColorFloat`) - The position to sample.
+    ///
+    /// # Returns
+    ///
+    /// - `Color` - The color at `t`.
+    #[must_use]
+    pub fn at(&self, t: ColorFloat) -> Color {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Color::TRANSPARENT,
+        };
+        if t.is_nan() || t <= first.position {
+            return first.color;
+        }
+        if t >= last.position {
+            return last.color;
+        }
+
+        // first stop strictly past t; the segment is [i - 1, i]
+        let i = self.stops.partition_point(|s| s.position <= t);
+        let (a, b) = (self.stops[i - 1], self.stops[i]);
+        let span = b.position - a.position;
+        if span <= 0.0 {
+            return b.color;
+        }
+
+        self.mix(a.color, b.color, (t - a.position) / span)
+    }
+
+    /// Sample the gradient `n` times, evenly from its first to its last stop.
+    ///
+    /// # Arguments
+    ///
+    /// - `n` (`usize`) - The number of samples.
+    ///
+    /// # Returns
+    ///
+    /// - `impl Iterator<Item = Color>` - The samples, in order.
+    pub fn samples(&self, n: usize) -> impl Iterator<Item = Color> + '_ {
+        let (start, end) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first.position, last.position),
+            _ => (0.0, 1.0),
+        };
+        let steps = n.saturating_sub(1).max(1) as ColorFloat;
+        (0..n).map(move |i| self.at(start + (end - start) * (i as ColorFloat / steps)))
+    }
+
+    /// Interpolate between two stop colors with premultiplied alpha.
+    fn mix(&self, a: Color, b: Color, t: ColorFloat) -> Color {
+        let [.., a8] = a.into_rgba();
+        let [.., b8] = b.into_rgba();
+        let alpha_a = a8 as ColorFloat / 255.0;
+        let alpha_b = b8 as ColorFloat / 255.0;
+        let alpha = alpha_a + (alpha_b - alpha_a) * t;
+
+        // premultiply, interpolate, then unpremultiply the rectangular components
+        let mix = |x: ColorFloat, y: ColorFloat| {
+            if alpha <= 0.0 {
+                return 0.0;
+            }
+            (x * alpha_a + (y * alpha_b - x * alpha_a) * t) / alpha
+        };
+        let alpha8 = (alpha.clamp(0.0, 1.0) * 255.0 + 0.5).floor() as u8;
+
+        match self.space {
+            InterpolationSpace::Srgb => {
+                let [ar, ag, ab, _] = a.into_rgba().map(|c| c as ColorFloat);
+                let [br, bg, bb, _] = b.into_rgba().map(|c| c as ColorFloat);
+                let channel = |x, y| (mix(x, y).clamp(0.0, 255.0) + 0.5).floor() as u8;
+                Color::new(channel(ar, br), channel(ag, bg), channel(ab, bb), alpha8)
+            }
+            InterpolationSpace::Linear => {
+                let [ar, ag, ab, _] = a.into_linear();
+                let [br, bg, bb, _] = b.into_linear();
+                Color::from_linear([mix(ar, br), mix(ag, bg), mix(ab, bb), alpha])
+            }
+            InterpolationSpace::Oklab => {
+                let [al, aa, ab] = a.into_oklab();
+                let [bl, ba, bb] = b.into_oklab();
+                Color::from_oklab([mix(al, bl), mix(aa, ba), mix(ab, bb)]).with_alpha(alpha8)
+            }
+            InterpolationSpace::Oklch => {
+                let [al, ac, ah] = a.into_oklch();
+                let [bl, bc, bh] = b.into_oklch();
+
+                // a gray has no meaningful hue, so borrow the other one
+                let (ah, bh) = if ac < 1e-5 {
+                    (bh, bh)
+                } else if bc < 1e-5 {
+                    (ah, ah)
+                } else {
+                    (ah, bh)
+                };
+                let (ah, bh) = self.hue.fixup(ah, bh);
+                let h = (ah + (bh - ah) * t).rem_euclid(360.0);
+
+                Color::from_oklch([mix(al, bl), mix(ac, bc).max(0.0), h]).with_alpha(alpha8)
+            }
+        }
+    }
+}
+
+impl HueInterpolation {
+    /// Adjust two hues (in 0.0..360.0) so a plain lerp between them follows this method.
+    #[inline]
+    fn fixup(self, mut h1: ColorFloat, mut h2: ColorFloat) -> (ColorFloat, ColorFloat) {
+        // source: https://www.w3.org/TR/css-color-4/#hue-interpolation
+        let d = h2 - h1;
+        match self {
+            HueInterpolation::Shorter => {
+                if d > 180.0 {
+                    h1 += 360.0;
+                } else if d < -180.0 {
+                    h2 += 360.0;
+                }
+            }
+            HueInterpolation::Longer => {
+                if 0.0 < d && d < 180.0 {
+                    h1 += 360.0;
+                } else if -180.0 < d && d <= 0.0 {
+                    h2 += 360.0;
+                }
+            }
+            HueInterpolation::Increasing => {
+                if d < 0.0 {
+                    h2 += 360.0;
+                }
+            }
+            HueInterpolation::Decreasing => {
+                if d > 0.0 {
+                    h1 += 360.0;
+                }
+            }
+        }
+        (h1, h2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Color = Color::new(255, 0, 0, 255);
+    const BLUE: Color = Color::new(0, 0, 255, 255);
+    const SPACES: [InterpolationSpace; 4] = [
+        InterpolationSpace::Srgb,
+        InterpolationSpace::Linear,
+        InterpolationSpace::Oklab,
+        InterpolationSpace::Oklch,
+    ];
+
+    fn hue_distance(a: ColorFloat, b: ColorFloat) -> ColorFloat {
+        let d = (a - b).rem_euclid(360.0);
+        d.min(360.0 - d)
+    }
+
+    #[test]
+    fn stops_are_sorted() {
+        let green = Color::new(0, 255, 0, 255);
+        let g = Gradient::from_stops([
+            ColorStop::new(BLUE, 1.0),
+            ColorStop::new(RED, 0.0),
+            ColorStop::new(green, 0.5),
+        ]);
+        let colors: Vec<Color> = g.stops().iter().map(|s| s.color).collect();
+        assert_eq!(colors, [RED, green, BLUE]);
+        assert_eq!(g.at(0.5), green);
+
+        let mut g = Gradient::new([RED, BLUE]);
+        g.add_stop(green, 0.25);
+        // a new stop at an existing position goes after it
+        g.add_stop(Color::WHITE, 0.25);
+        let positions: Vec<ColorFloat> = g.stops().iter().map(|s| s.position).collect();
+        assert_eq!(positions, [0.0, 0.25, 0.25, 1.0]);
+        assert_eq!(g.stops()[1].color, green);
+        assert_eq!(g.stops()[2].color, Color::WHITE);
+    }
+
+    #[test]
+    fn new_spaces_stops_evenly() {
+        let g = Gradient::new([RED, Color::WHITE, BLUE]);
+        let positions: Vec<ColorFloat> = g.stops().iter().map(|s| s.position).collect();
+        assert_eq!(positions, [0.0, 0.5, 1.0]);
+        assert_eq!(Gradient::new([RED]).stops()[0].position, 0.0);
+        assert_eq!(Gradient::new([]).at(0.5), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn coincident_stops_make_a_hard_edge() {
+        let g = Gradient::from_stops([
+            ColorStop::new(RED, 0.0),
+            ColorStop::new(RED, 0.5),
+            ColorStop::new(BLUE, 0.5),
+            ColorStop::new(BLUE, 1.0),
+        ]);
+        for space in SPACES {
+            let g = g.clone().with_space(space);
+            assert_eq!(g.at(0.25), RED, "{space:?}");
+            assert_eq!(g.at(0.4999), RED, "{space:?}");
+            assert_eq!(g.at(0.5), BLUE, "{space:?}");
+            assert_eq!(g.at(0.75), BLUE, "{space:?}");
+        }
+    }
+
+    #[test]
+    fn clamps_outside_the_stops() {
+        let g = Gradient::from_stops([ColorStop::new(RED, 0.2), ColorStop::new(BLUE, 0.8)]);
+        assert_eq!(g.at(-1.0), RED);
+        assert_eq!(g.at(0.0), RED);
+        assert_eq!(g.at(0.2), RED);
+        assert_eq!(g.at(0.8), BLUE);
+        assert_eq!(g.at(2.0), BLUE);
+        assert_eq!(g.at(ColorFloat::NAN), RED);
+
+        let samples: Vec<Color> = g.samples(3).collect();
+        assert_eq!(samples[0], RED);
+        assert_eq!(samples[2], BLUE);
+    }
+
+    #[test]
+    fn transparent_stops_have_no_gray_fringe() {
+        // `transparent` is transparent black, which would darken the middle without premultiplying
+        for space in SPACES {
+            let gradient = Gradient::new([Color::TRANSPARENT, Color::WHITE]).with_space(space);
+            for t in [0.25, 0.5, 0.75] {
+                let [r, g, b, a] = gradient.at(t).into_rgba();
+                assert_eq!([r, g, b], [255; 3], "{space:?} at {t}");
+                let expected = (t * 255.0 + 0.5).floor() as u8;
+                assert!(a.abs_diff(expected) <= 1, "{space:?} at {t}: alpha {a}");
+            }
+        }
+    }
+
+    #[test]
+    fn hue_interpolation_across_the_seam() {
+        let a = Color::from_oklch([0.6, 0.1, 350.0]);
+        let b = Color::from_oklch([0.6, 0.1, 10.0]);
+        // midpoint hue going a -> b and b -> a
+        let cases = [
+            (HueInterpolation::Shorter, 0.0, 0.0),
+            (HueInterpolation::Longer, 180.0, 180.0),
+            (HueInterpolation::Increasing, 0.0, 180.0),
+            (HueInterpolation::Decreasing, 180.0, 0.0),
+        ];
+        for (method, forward, backward) in cases {
+            let mid = |from: Color, to: Color| {
+                Gradient::new([from, to])
+                    .with_space(InterpolationSpace::Oklch)
+                    .with_hue_interpolation(method)
+                    .at(0.5)
+                    .into_oklch()[2]
+            };
+            let h = mid(a, b);
+            assert!(hue_distance(h, forward) < 3.0, "{method:?} forward: {h}");
+            let h = mid(b, a);
+            assert!(hue_distance(h, backward) < 3.0, "{method:?} backward: {h}");
+        }
+    }
+
+    #[test]
+    fn gray_stops_borrow_the_other_hue() {
+        let g = Gradient::new([Color::new(128, 128, 128, 255), BLUE])
+            .with_space(InterpolationSpace::Oklch);
+        let [_, _, h] = g.at(0.5).into_oklch();
+        assert!(hue_distance(h, BLUE.into_oklch()[2]) < 3.0, "{h}");
+    }
+}
@@ -0,0 +1,384 @@
+#![allow(dead_code)]
+
+// Color harmonies and tonal scales for building palettes from a single color.
+// everything works in OKLCH: harmonies rotate the hue at a fixed lightness and chroma,
+// and tonal scales step the lightness evenly at a fixed hue and chroma. OKLCH lightness
+// is close to perceptual, so the steps look even. out of gamut results are gamut mapped
+// by `Color::from_oklch`, which lowers chroma and keeps the hue.
+//
+// nothing here allocates: harmonies are fixed-size arrays and scales are arrays or iterators.
+
+#[cfg(not(feature = "std"))]
+use crate::color::math::FloatMath;
+use crate::color::{
+    ColorFloat,
+    model::{Color, GRAY_CHROMA},
+};
+
+impl Color {
+    /// Rotate a color's hue in OKLCH, keeping lightness, chroma and alpha.
+    ///
+    /// Grays have no hue, so they are returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to rotate.
+    /// - `degrees` (`ColorFloat`) - The rotation, positive or negative.
+    ///
+    /// # Returns
+    ///
+    /// - `Color` - The rotated color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let tomato = Color::new(255, 99, 71, 255);
+    /// let rotated = tomato.rotate_hue(45.0);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn rotate_hue(self, degrees: ColorFloat) -> Color {
+        let [l, c, h] = self.into_oklch();
+        if c < GRAY_CHROMA {
+            return self;
+        }
+        let [.., a] = self.into_rgba();
+        Color::from_oklch([l, c, (h + degrees).rem_euclid(360.0)]).with_alpha(a)
+    }
+
+    /// Build a complementary pair: the color and the color opposite it on the hue wheel.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The base color.
+    ///
+    /// # Returns
+    ///
+    /// - `[Color; 2]` - The base color and its complement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let [brand, accent] = Color::new(30, 144, 255, 255).complementary();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn complementary(self) -> [Color; 2] {
+        [self, self.rotate_hue(180.0)]
+    }
+
+    /// Build an analogous palette: the color and its neighbors on either side.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The base color.
+    /// - `spread` (`ColorFloat`) - The hue distance to each neighbor in degrees.
+    ///   30.0 is typical.
+    ///
+    /// # Returns
+    ///
+    /// - `[Color; 3]` - The base color, the neighbor at `+spread`, and the
+    ///   neighbor at `-spread`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let [base, warmer, cooler] = Color::new(46, 139, 87, 255).analogous(30.0);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn analogous(self, spread: ColorFloat) -> [Color; 3] {
+        [self, self.rotate_hue(spread), self.rotate_hue(-spread)]
+    }
+
+    /// Build a triadic palette: three colors evenly spaced on the hue wheel.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The base color.
+    ///
+    /// # Returns
+    ///
+    /// - `[Color; 3]` - The base color, then the colors at +120 and +240 degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let palette = Color::new(138, 43, 226, 255).triadic();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn triadic(self) -> [Color; 3] {
+        [self, self.rotate_hue(120.0), self.rotate_hue(240.0)]
+    }
+
+    /// Build a tetradic (square) palette: four colors evenly spaced on the hue wheel.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The base color.
+    ///
+    /// # Returns
+    ///
+    /// - `[Color; 4]` - The base color, then the colors at +90, +180 and +270 degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let palette = Color::new(255, 140, 0, 255).tetradic();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn tetradic(self) -> [Color; 4] {
+        [
+            self,
+            self.rotate_hue(90.0),
+            self.rotate_hue(180.0),
+            self.rotate_hue(270.0),
+        ]
+    }
+
+    /// Build a split-complementary palette: the color and the two colors either
+    /// side of its complement.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The base color.
+    /// - `spread` (`ColorFloat`) - The hue distance from the complement in degrees.
+    ///   30.0 is typical.
+    ///
+    /// # Returns
+    ///
+    /// - `[Color; 3]` - The base color, then the colors at `180 - spread` and
+    ///   `180 + spread` degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let palette = Color::new(220, 20, 60, 255).split_complementary(30.0);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn split_complementary(self, spread: ColorFloat) -> [Color; 3] {
+        [
+            self,
+            self.rotate_hue(180.0 - spread),
+            self.rotate_hue(180.0 + spread),
+        ]
+    }
+
+    /// Build a tonal scale: `N` shades of the color with evenly stepped OKLCH lightness.
+    ///
+    /// Hue, chroma (as far as the gamut allows) and alpha are kept. The first
+    /// shade has lightness `lightest` and the last has `darkest`, so a scale
+    /// runs light to dark like the 50-900 shades of common design systems.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The base color.
+    /// - `lightest` (`ColorFloat`) - The OKLCH lightness of the first shade.
+    /// - `darkest` (`ColorFloat`) - The OKLCH lightness of the last shade.
+    ///   Both values will be clamped between and including 0.0 and 1.0.
+    ///
+    /// # Returns
+    ///
+    /// - `[Color; N]` - The shades. A single shade uses `lightest`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let teal = Color::new(0, 128, 128, 255);
+    /// let shades: [Color; 9] = teal.tonal_scale(0.97, 0.25);
+    /// ```
+    #[must_use]
+    pub fn tonal_scale<const N: usize>(
+        self,
+        lightest: ColorFloat,
+        darkest: ColorFloat,
+    ) -> [Color; N] {
+        let mut tones = self.tones(N, lightest, darkest);
+        core::array::from_fn(|_| tones.next().unwrap_or(self))
+    }
+
+    /// Iterate over `n` shades of the color with evenly stepped OKLCH lightness.
+    ///
+    /// Works like `Color::tonal_scale` when the number of shades isn't known
+    /// at compile time.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The base color.
+    /// - `n` (`usize`) - The number of shades.
+    /// - `lightest` (`ColorFloat`) - The OKLCH lightness of the first shade.
+    /// - `darkest` (`ColorFloat`) - The OKLCH lightness of the last shade.
+    ///   Both values will be clamped between and including 0.0 and 1.0.
+    ///
+    /// # Returns
+    ///
+    /// - `impl Iterator<Item = Color>` - The shades, lightest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let plum = Color::new(221, 160, 221, 255);
+    /// for shade in plum.tones(5, 0.95, 0.3) {
+    ///     println!("{shade}");
+    /// }
+    /// ```
+    pub fn tones(
+        self,
+        n: usize,
+        lightest: ColorFloat,
+        darkest: ColorFloat,
+    ) -> impl Iterator<Item = Color> {
+        let [_, c, h] = self.into_oklch();
+        let [.., a] = self.into_rgba();
+        let lightest = lightest.clamp(0.0, 1.0);
+        let darkest = darkest.clamp(0.0, 1.0);
+        let steps = n.saturating_sub(1).max(1) as ColorFloat;
+
+        (0..n).map(move |i| {
+            let l = lightest + (darkest - lightest) * (i as ColorFloat / steps);
+            Color::from_oklch_neutral([l, c, h]).with_alpha(a)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hue_distance(a: ColorFloat, b: ColorFloat) -> ColorFloat {
+        let d = (a - b).rem_euclid(360.0);
+        d.min(360.0 - d)
+    }
+
+    #[test]
+    fn rotated_hues_land_at_the_requested_offsets() {
+        for base_hue in (0..360).step_by(30) {
+            // low enough chroma to stay in gamut at every hue
+            let base = Color::from_oklch([0.7, 0.08, base_hue as ColorFloat]);
+            let [l, c, h] = base.into_oklch();
+            for degrees in [30.0, -30.0, 90.0, 120.0, 180.0, 240.0, 270.0, 400.0] {
+                let [rl, rc, rh] = base.rotate_hue(degrees).into_oklch();
+                let expected = (h + degrees).rem_euclid(360.0);
+                assert!(
+                    hue_distance(rh, expected) < 2.0,
+                    "{h} + {degrees} gave {rh}"
+                );
+                assert!(
+                    (rl - l).abs() < 0.005 && (rc - c).abs() < 0.005,
+                    "{base:?} {degrees}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn schemes_use_their_offsets() {
+        let c = Color::new(200, 100, 50, 255);
+        assert_eq!(c.complementary(), [c, c.rotate_hue(180.0)]);
+        assert_eq!(
+            c.analogous(20.0),
+            [c, c.rotate_hue(20.0), c.rotate_hue(-20.0)]
+        );
+        assert_eq!(c.triadic(), [c, c.rotate_hue(120.0), c.rotate_hue(240.0)]);
+        assert_eq!(
+            c.tetradic(),
+            [
+                c,
+                c.rotate_hue(90.0),
+                c.rotate_hue(180.0),
+                c.rotate_hue(270.0)
+            ]
+        );
+        assert_eq!(
+            c.split_complementary(30.0),
+            [c, c.rotate_hue(150.0), c.rotate_hue(210.0)]
+        );
+    }
+
+    #[test]
+    fn grays_are_unchanged() {
+        for v in (0..=255u8).step_by(5) {
+            let gray = Color::new(v, v, v, 200);
+            assert_eq!(gray.rotate_hue(90.0), gray);
+            assert_eq!(gray.triadic(), [gray; 3]);
+        }
+    }
+
+    #[test]
+    fn alpha_is_kept() {
+        let c = Color::new(30, 144, 255, 77);
+        for harmony in c.tetradic() {
+            assert_eq!(harmony.into_rgba()[3], 77);
+        }
+        for shade in c.tones(5, 0.9, 0.3) {
+            assert_eq!(shade.into_rgba()[3], 77);
+        }
+    }
+
+    #[test]
+    fn tonal_scale_steps_lightness_evenly() {
+        let (lightest, darkest) = (0.9, 0.3);
+        // muted enough that no shade needs gamut mapping, which can nudge lightness
+        let muted = Color::from_oklch([0.6, 0.04, 200.0]);
+        for base in [muted, Color::new(128, 128, 128, 255)] {
+            let shades: [Color; 7] = base.tonal_scale(lightest, darkest);
+            let step = (darkest - lightest) / 6.0;
+            for (i, shade) in shades.iter().enumerate() {
+                let l = shade.into_oklch()[0];
+                let expected = lightest + step * i as ColorFloat;
+                assert!(
+                    (l - expected).abs() < 0.005,
+                    "{base:?} shade {i}: {l} vs {expected}"
+                );
+            }
+            assert!(shades.into_iter().eq(base.tones(7, lightest, darkest)));
+        }
+    }
+
+    #[test]
+    fn tonal_scale_is_monotonic() {
+        for base in [Color::new(0, 128, 128, 255), Color::new(255, 0, 255, 255)] {
+            let shades: [Color; 12] = base.tonal_scale(0.97, 0.25);
+            for pair in shades.windows(2) {
+                assert!(
+                    pair[0].into_oklch()[0] > pair[1].into_oklch()[0],
+                    "{pair:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn zero_and_one_tones() {
+        let teal = Color::new(0, 128, 128, 255);
+        assert_eq!(teal.tones(0, 0.9, 0.3).count(), 0);
+
+        // a single shade uses `lightest`
+        let one: Vec<Color> = teal.tones(1, 0.9, 0.3).collect();
+        assert_eq!(one.len(), 1);
+        assert!((one[0].into_oklch()[0] - 0.9).abs() < 0.01);
+        let [single]: [Color; 1] = teal.tonal_scale(0.9, 0.3);
+        assert_eq!(single, one[0]);
+        let empty: [Color; 0] = teal.tonal_scale(0.9, 0.3);
+        assert!(empty.is_empty());
+    }
+}
@@ -2,77 +2,364 @@
 // sacrificing memory for speed (replacing heavy pow calculations).
 // This should primarily be used for CPU-based calculations,
 // since GPUs have built-in pow functions and don't need this optimization.
+//
+// every table is built by const evaluation, so there's no initialization or
+// synchronization at runtime: a lookup is a plain indexed load from read-only memory.
+// tables that nothing uses are dropped by the linker.
 
 #![allow(dead_code)]
 
-use std::sync::OnceLock;
+#[cfg(not(feature = "std"))]
+use crate::color::math::FloatMath;
+
+use crate::color::ColorFloat;
 
 /// linear -> sRGB table size
 const N_ENC: usize = 4096;
 
-static SRGB_TO_LINEAR_F32: OnceLock<[f32; 256]> = OnceLock::new();
-static LINEAR_TO_SRGB_U8: OnceLock<[u8; N_ENC]> = OnceLock::new();
+/// sRGB u8 -> linear f32.
+static SRGB_TO_LINEAR_F32: [f32; 256] = build_srgb_to_linear_f32();
+/// sRGB u8 -> linear f64.
+static SRGB_TO_LINEAR_F64: [f64; 256] = build_srgb_to_linear_f64();
+/// sRGB u8 -> linear u16 (0-65535).
+static SRGB_TO_LINEAR_U16: [u16; 256] = build_srgb_to_linear_u16();
+/// linear (`N_ENC` even steps over 0.0-1.0) -> sRGB u8, interpolated by the float encoders.
+static LINEAR_TO_SRGB_U8: [u8; N_ENC] = build_linear_to_srgb_u8::<N_ENC>();
+/// linear u16 -> sRGB u8, one entry per u16 so no interpolation is needed.
+static LINEAR_U16_TO_SRGB_U8: [u8; 65536] = build_linear_to_srgb_u8::<65536>();
 
-#[inline]
-fn build_srgb_to_linear_f32() -> [f32; 256] {
-    let mut t = [0.0f32; 256];
-    for (v, item) in t.iter_mut().enumerate() {
-        let x = (v as f32) / 255.0;
-        *item = if x <= 0.04045 {
-            x / 12.92
-        } else {
-            ((x + 0.055) / 1.055).powf(2.4)
-        };
+// --- const math --- //
+// core's float methods like `powf` aren't const, so the tables use these instead.
+// they're accurate to a few ulps of f64, far below what any table entry can hold.
+
+const LN_2: f64 = core::f64::consts::LN_2;
+
+/// Natural log for finite `x > 0.0`.
+const fn const_ln(x: f64) -> f64 {
+    // split x into m * 2^e with m in [sqrt(2)/2, sqrt(2)), so the series converges fast
+    let bits = x.to_bits();
+    let mut e = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let mut m = f64::from_bits((bits & ((1 << 52) - 1)) | (1023 << 52));
+    if m > core::f64::consts::SQRT_2 {
+        m /= 2.0;
+        e += 1;
+    }
+
+    // ln(m) = 2 * atanh(s) = 2 * (s + s^3/3 + s^5/5 + ...), with |s| <= 0.172
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    let mut term = s;
+    let mut sum = 0.0;
+    let mut k = 1.0;
+    while k < 40.0 {
+        sum += term / k;
+        term *= s2;
+        k += 2.0;
+    }
+
+    2.0 * sum + e as f64 * LN_2
+}
+
+/// e^x for `x` in about -700.0..700.0.
+const fn const_exp(x: f64) -> f64 {
+    // x = k * ln(2) + r with |r| <= ln(2) / 2, then e^x = 2^k * e^r
+    let kf = x / LN_2;
+    let k = if kf >= 0.0 {
+        (kf + 0.5) as i64
+    } else {
+        (kf - 0.5) as i64
+    };
+    let r = x - k as f64 * LN_2;
+
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut n = 1.0;
+    while n < 24.0 {
+        term *= r / n;
+        sum += term;
+        n += 1.0;
+    }
+
+    sum * f64::from_bits(((k + 1023) as u64) << 52)
+}
+
+/// x^y for `x >= 0.0`.
+const fn const_powf(x: f64, y: f64) -> f64 {
+    if x == 0.0 {
+        return 0.0;
+    }
+    const_exp(y * const_ln(x))
+}
+
+/// The sRGB decoding function (IEC 61966-2-1), on 0.0-1.0.
+const fn srgb_to_linear(x: f64) -> f64 {
+    if x <= 0.04045 {
+        x / 12.92
+    } else {
+        const_powf((x + 0.055) / 1.055, 2.4)
+    }
+}
+
+// --- table builders --- //
+
+const fn build_srgb_to_linear_f64() -> [f64; 256] {
+    let mut t = [0.0; 256];
+    let mut v = 0;
+    while v < 256 {
+        t[v] = srgb_to_linear(v as f64 / 255.0);
+        v += 1;
     }
     t
 }
 
-#[inline]
-fn build_linear_to_srgb_u8() -> [u8; N_ENC] {
-    let mut t = [0u8; N_ENC];
-    for (i, item) in t.iter_mut().enumerate() {
-        let x = (i as f32) / (N_ENC as f32 - 1.0); // 0..1
-        let y = if x <= 0.003_130_8 {
-            12.92 * x
-        } else {
-            1.055 * x.powf(1.0 / 2.4) - 0.055
-        };
-        *item = ((y.clamp(0.0, 1.0) * 255.0) + 0.5).floor() as u8;
+const fn build_srgb_to_linear_f32() -> [f32; 256] {
+    let wide = build_srgb_to_linear_f64();
+    let mut t = [0.0; 256];
+    let mut v = 0;
+    while v < 256 {
+        t[v] = wide[v] as f32;
+        v += 1;
     }
     t
 }
 
-#[inline]
-fn get_srgb_to_linear_f32() -> &'static [f32; 256] {
-    SRGB_TO_LINEAR_F32.get_or_init(build_srgb_to_linear_f32)
+const fn build_srgb_to_linear_u16() -> [u16; 256] {
+    let wide = build_srgb_to_linear_f64();
+    let mut t = [0; 256];
+    let mut v = 0;
+    while v < 256 {
+        t[v] = (wide[v] * 65535.0 + 0.5) as u16;
+        v += 1;
+    }
+    t
 }
 
-#[inline]
-fn get_linear_to_srgb_u8() -> &'static [u8; N_ENC] {
-    LINEAR_TO_SRGB_U8.get_or_init(build_linear_to_srgb_u8)
+/// Encode `N` evenly spaced linear values over 0.0-1.0, rounding to the nearest code.
+const fn build_linear_to_srgb_u8<const N: usize>() -> [u8; N] {
+    // code k is the answer from the linear value where the encoded value reaches
+    // k - 0.5 (inclusive) up to where it reaches k + 0.5. walk up those thresholds
+    // instead of encoding every entry, which keeps the 64K table cheap to build.
+    let mut thresholds = [0.0; 256];
+    let mut k = 1;
+    while k < 256 {
+        thresholds[k] = srgb_to_linear((k as f64 - 0.5) / 255.0);
+        k += 1;
+    }
+
+    let mut t = [0; N];
+    let mut code = 0;
+    let mut i = 0;
+    while i < N {
+        let x = i as f64 / (N - 1) as f64;
+        while code < 255 && x >= thresholds[code + 1] {
+            code += 1;
+        }
+        t[i] = code as u8;
+        i += 1;
+    }
+    t
 }
 
 // we'll hide the public api behind a feature
 
-#[cfg(feature = "srgb_lut")]
 #[inline]
 pub(crate) fn decode_srgb_lut_f32(v: u8) -> f32 {
-    get_srgb_to_linear_f32()[v as usize]
+    SRGB_TO_LINEAR_F32[v as usize]
+}
+
+#[inline]
+pub(crate) fn decode_srgb_lut_f64(v: u8) -> f64 {
+    SRGB_TO_LINEAR_F64[v as usize]
 }
 
-#[cfg(feature = "srgb_lut")]
 #[inline]
 pub(crate) fn encode_srgb_lut_f32(x: f32) -> u8 {
     let x = x.clamp(0.0, 1.0);
     let idx = x * (N_ENC as f32 - 1.0);
     let i = idx as usize;
     if i >= N_ENC - 1 {
-        return get_linear_to_srgb_u8()[N_ENC - 1];
+        return LINEAR_TO_SRGB_U8[N_ENC - 1];
     }
     let f = idx - i as f32;
-    let a = get_linear_to_srgb_u8()[i] as u16;
-    let b = get_linear_to_srgb_u8()[i + 1] as u16;
+    let a = LINEAR_TO_SRGB_U8[i];
+    let b = LINEAR_TO_SRGB_U8[i + 1];
     // linear interp in integer space, then round
     let y = a as f32 + (b as f32 - a as f32) * f;
     (y + 0.5).floor() as u8
 }
+
+#[inline]
+pub(crate) fn encode_srgb_lut_f64(x: f64) -> u8 {
+    let x = x.clamp(0.0, 1.0);
+    let idx = x * (N_ENC as f64 - 1.0);
+    let i = idx as usize;
+    if i >= N_ENC - 1 {
+        return LINEAR_TO_SRGB_U8[N_ENC - 1];
+    }
+    let f = idx - i as f64;
+    let a = LINEAR_TO_SRGB_U8[i];
+    let b = LINEAR_TO_SRGB_U8[i + 1];
+    // linear interp in integer space, then round
+    let y = a as f64 + (b as f64 - a as f64) * f;
+    (y + 0.5).floor() as u8
+}
+
+/// Decode an 8 bit sRGB value with the table matching `ColorFloat`.
+#[inline]
+pub(crate) fn decode_srgb_lut(v: u8) -> ColorFloat {
+    #[cfg(feature = "color_double_precision")]
+    {
+        decode_srgb_lut_f64(v)
+    }
+    #[cfg(not(feature = "color_double_precision"))]
+    {
+        decode_srgb_lut_f32(v)
+    }
+}
+
+/// Encode a linear `ColorFloat` into an 8 bit sRGB value with the table.
+#[inline]
+pub(crate) fn encode_srgb_lut(x: ColorFloat) -> u8 {
+    #[cfg(feature = "color_double_precision")]
+    {
+        encode_srgb_lut_f64(x)
+    }
+    #[cfg(not(feature = "color_double_precision"))]
+    {
+        encode_srgb_lut_f32(x)
+    }
+}
+
+/// Decode an 8 bit sRGB value into 16 bit linear light, for integer pipelines.
+///
+/// # Arguments
+///
+/// - `v` (`u8`) - The sRGB value.
+///
+/// # Returns
+///
+/// - `u16` - The linear value, 0 for black and 65535 for full intensity.
+///
+/// # Examples
+///
+/// ```
+/// use codimate::color::lut::decode_srgb_u16;
+///
+/// assert_eq!(decode_srgb_u16(255), 65535);
+/// ```
+#[must_use]
+#[inline]
+pub fn decode_srgb_u16(v: u8) -> u16 {
+    SRGB_TO_LINEAR_U16[v as usize]
+}
+
+/// Encode 16 bit linear light into an 8 bit sRGB value, for integer pipelines.
+///
+/// The result is the correctly rounded sRGB value; no interpolation is done.
+///
+/// # Arguments
+///
+/// - `lin` (`u16`) - The linear value, 0 for black and 65535 for full intensity.
+///
+/// # Returns
+///
+/// - `u8` - The sRGB value.
+///
+/// # Examples
+///
+/// ```
+/// use codimate::color::lut::{decode_srgb_u16, encode_srgb_u16};
+///
+/// // averaging in linear light
+/// let mid = (decode_srgb_u16(0) as u32 + decode_srgb_u16(255) as u32) / 2;
+/// let srgb = encode_srgb_u16(mid as u16); // 188
+/// ```
+#[must_use]
+#[inline]
+pub fn encode_srgb_u16(lin: u16) -> u8 {
+    LINEAR_U16_TO_SRGB_U8[lin as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the reference formulas, in f64 whatever `ColorFloat` is.
+    // `Color`'s own versions are these tables when `srgb_lut` is on, so they can't be used here.
+
+    fn decode_srgb(v: u8) -> f64 {
+        let srgb = v as f64 / 255.0;
+        if srgb <= 0.04045 {
+            srgb / 12.92
+        } else {
+            ((srgb + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    fn encode_srgb(lin: f64) -> u8 {
+        let l = lin.clamp(0.0, 1.0);
+        if l <= 0.0031308 {
+            ((12.92 * l) * 255.0 + 0.5).floor() as u8
+        } else {
+            ((1.055 * l.powf(1.0 / 2.4) - 0.055) * 255.0 + 0.5).floor() as u8
+        }
+    }
+
+    #[test]
+    fn decode_tables_match_the_formula() {
+        for v in 0..=255u8 {
+            let want = decode_srgb(v);
+            let i = v as usize;
+            assert!((SRGB_TO_LINEAR_F64[i] - want).abs() <= 1e-12, "f64 {v}");
+            assert!(
+                (SRGB_TO_LINEAR_F32[i] - want as f32).abs() <= f32::EPSILON * want as f32,
+                "f32 {v}"
+            );
+            assert_eq!(
+                SRGB_TO_LINEAR_U16[i],
+                (want * 65535.0 + 0.5) as u16,
+                "u16 {v}"
+            );
+            assert_eq!(decode_srgb_lut_f64(v), SRGB_TO_LINEAR_F64[i]);
+            assert_eq!(decode_srgb_lut_f32(v), SRGB_TO_LINEAR_F32[i]);
+            assert_eq!(decode_srgb_u16(v), SRGB_TO_LINEAR_U16[i]);
+        }
+    }
+
+    #[test]
+    fn encode_tables_are_within_one_of_the_formula() {
+        const STEPS: u32 = 100_000;
+        for i in 0..=STEPS {
+            let lin = i as f64 / STEPS as f64;
+            let want = encode_srgb(lin);
+            assert!(encode_srgb_lut_f64(lin).abs_diff(want) <= 1, "f64 {lin}");
+            assert!(
+                encode_srgb_lut_f32(lin as f32).abs_diff(want) <= 1,
+                "f32 {lin}"
+            );
+        }
+        for lin in 0..=u16::MAX {
+            let want = encode_srgb(lin as f64 / 65535.0);
+            assert!(encode_srgb_u16(lin).abs_diff(want) <= 1, "u16 {lin}");
+        }
+        // out of range input clamps
+        assert_eq!(encode_srgb_lut_f32(-1.0), 0);
+        assert_eq!(encode_srgb_lut_f64(2.0), 255);
+    }
+
+    #[test]
+    fn u16_round_trips() {
+        for v in 0..=255u8 {
+            assert_eq!(encode_srgb_u16(decode_srgb_u16(v)), v);
+        }
+    }
+
+    #[test]
+    fn float_round_trips() {
+        for v in 0..=255u8 {
+            assert_eq!(encode_srgb_lut_f32(decode_srgb_lut_f32(v)), v);
+            assert_eq!(encode_srgb_lut_f64(decode_srgb_lut_f64(v)), v);
+            assert_eq!(encode_srgb_lut(decode_srgb_lut(v)), v);
+        }
+    }
+}
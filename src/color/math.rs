@@ -0,0 +1,89 @@
+#![allow(dead_code)]
+
+// Float math for no_std builds. core leaves out the float methods that need a libm
+// (`powf`, `sqrt`, `floor`, `sin`, ...), so this trait puts them back under the same
+// names, backed by the pure Rust `libm` crate. call sites stay unchanged: files import
+// the trait only when std is off, and with std the inherent methods are used instead.
+
+/// The std float methods the color module uses, for `f32` and `f64`.
+pub(crate) trait FloatMath: Sized {
+    fn floor(self) -> Self;
+    fn round(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn exp(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_float_math {
+    ($t:ty, $floor:ident, $round:ident, $sqrt:ident, $cbrt:ident, $pow:ident, $exp:ident, $sin:ident, $cos:ident, $atan2:ident) => {
+        impl FloatMath for $t {
+            #[inline]
+            fn floor(self) -> Self {
+                libm::$floor(self)
+            }
+
+            #[inline]
+            fn round(self) -> Self {
+                libm::$round(self)
+            }
+
+            #[inline]
+            fn sqrt(self) -> Self {
+                libm::$sqrt(self)
+            }
+
+            #[inline]
+            fn cbrt(self) -> Self {
+                libm::$cbrt(self)
+            }
+
+            #[inline]
+            fn powf(self, n: Self) -> Self {
+                libm::$pow(self, n)
+            }
+
+            #[inline]
+            fn powi(self, n: i32) -> Self {
+                libm::$pow(self, n as $t)
+            }
+
+            #[inline]
+            fn exp(self) -> Self {
+                libm::$exp(self)
+            }
+
+            #[inline]
+            fn sin(self) -> Self {
+                libm::$sin(self)
+            }
+
+            #[inline]
+            fn cos(self) -> Self {
+                libm::$cos(self)
+            }
+
+            #[inline]
+            fn atan2(self, other: Self) -> Self {
+                libm::$atan2(self, other)
+            }
+
+            #[inline]
+            fn rem_euclid(self, rhs: Self) -> Self {
+                // same as std: the remainder, made non-negative
+                let r = self % rhs;
+                if r < 0.0 { r + rhs.abs() } else { r }
+            }
+        }
+    };
+}
+
+impl_float_math!(
+    f32, floorf, roundf, sqrtf, cbrtf, powf, expf, sinf, cosf, atan2f
+);
+impl_float_math!(f64, floor, round, sqrt, cbrt, pow, exp, sin, cos, atan2);
@@ -1,9 +1,25 @@
-#![cfg_attr(not(feature = "std"), no_std)]
-
+pub mod blend;
+pub mod contrast;
+pub mod css;
+pub mod cvd;
+pub mod difference;
+pub mod gamut;
+#[cfg(feature = "alloc")]
+pub mod gradient;
+pub mod harmony;
 #[cfg(feature = "srgb_lut")]
 pub mod lut;
+#[cfg(not(feature = "std"))]
+mod math;
 pub mod model;
+pub mod named;
 pub mod parse;
+pub mod premul;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod temperature;
+
+pub use model::{BlendMode, Color, CompositeOp};
 
 #[cfg(feature = "color_double_precision")]
 pub type ColorFloat = f64;
@@ -1,11 +1,13 @@
 #![allow(dead_code)]
 
-use std::fmt::{self};
+use core::fmt::{self};
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
-use crate::color::ColorFloat;
+#[cfg(not(feature = "std"))]
+use crate::color::math::FloatMath;
+use crate::color::{ColorFloat, gamut};
 
 /// An enum naming the supported color blending modes.
 /// Most descriptions and implementations of these blend modes
@@ -108,6 +110,267 @@ pub enum BlendMode {
     Luminosity,
 }
 
+impl BlendMode {
+    /// Every blend mode, in declaration order.
+    pub const ALL: [BlendMode; 16] = [
+        BlendMode::Normal,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Overlay,
+        BlendMode::Darken,
+        BlendMode::Lighten,
+        BlendMode::ColorDodge,
+        BlendMode::ColorBurn,
+        BlendMode::HardLight,
+        BlendMode::SoftLight,
+        BlendMode::Difference,
+        BlendMode::Exclusion,
+        BlendMode::Hue,
+        BlendMode::Saturation,
+        BlendMode::Color,
+        BlendMode::Luminosity,
+    ];
+
+    /// Get the kebab-case name of a blend mode, as used by CSS `mix-blend-mode`.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`BlendMode`) - The blend mode to name.
+    ///
+    /// # Returns
+    ///
+    /// - `&'static str` - The name, like `"color-dodge"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::BlendMode;
+    ///
+    /// let name = BlendMode::ColorDodge.as_str();
+    /// ```
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        use BlendMode::*;
+
+        match self {
+            Normal => "normal",
+            Multiply => "multiply",
+            Screen => "screen",
+            Overlay => "overlay",
+            Darken => "darken",
+            Lighten => "lighten",
+            ColorDodge => "color-dodge",
+            ColorBurn => "color-burn",
+            HardLight => "hard-light",
+            SoftLight => "soft-light",
+            Difference => "difference",
+            Exclusion => "exclusion",
+            Hue => "hue",
+            Saturation => "saturation",
+            Color => "color",
+            Luminosity => "luminosity",
+        }
+    }
+}
+
+/// The error returned when a string isn't a blend mode name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseBlendModeError;
+
+impl fmt::Display for ParseBlendModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown blend mode")
+    }
+}
+#[cfg(feature = "std")]
+impl core::error::Error for ParseBlendModeError {}
+
+impl core::str::FromStr for BlendMode {
+    type Err = ParseBlendModeError;
+
+    /// Parse a kebab-case blend mode name (ASCII case is ignored).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BlendMode::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or(ParseBlendModeError)
+    }
+}
+
+impl fmt::Display for BlendMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An enum naming the Porter-Duff compositing operators.
+/// These decide how much of the source and of the backdrop survive where the two
+/// overlap, independently of the `BlendMode` that mixes their colors.
+/// Definitions come from the W3C: https://www.w3.org/TR/compositing-1/#porterduffcompositingoperators.
+///
+/// # Variants
+///
+/// - `Clear` - Neither the source nor the backdrop is shown.
+///
+/// - `Copy` - Only the source is shown.
+///
+/// - `SourceOver` - The source is placed over the backdrop. This is the usual "paint" operator.
+///
+/// - `SourceIn` - The source is shown only where the backdrop is. The backdrop is dropped.
+///
+/// - `SourceOut` - The source is shown only where the backdrop isn't. The backdrop is dropped.
+///
+/// - `SourceAtop` - The source is placed over the backdrop, but only where the backdrop is.
+///
+/// - `DestinationOver` - The backdrop is placed over the source.
+///
+/// - `DestinationIn` - The backdrop is kept only where the source is. Useful as a mask.
+///
+/// - `DestinationOut` - The backdrop is kept only where the source isn't. Useful for cutouts.
+///
+/// - `DestinationAtop` - The backdrop is placed over the source, but only where the source is.
+///
+/// - `Xor` - The parts of the source and backdrop that don't overlap are shown.
+///
+/// - `PlusLighter` - The source and backdrop are added together, clamped to 1.
+///   Good for cross-fades and glow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CompositeOp {
+    Clear,
+    Copy,
+    #[default]
+    SourceOver,
+    SourceIn,
+    SourceOut,
+    SourceAtop,
+    DestinationOver,
+    DestinationIn,
+    DestinationOut,
+    DestinationAtop,
+    Xor,
+    PlusLighter,
+}
+
+impl CompositeOp {
+    /// Every compositing operator, in declaration order.
+    pub const ALL: [CompositeOp; 12] = [
+        CompositeOp::Clear,
+        CompositeOp::Copy,
+        CompositeOp::SourceOver,
+        CompositeOp::SourceIn,
+        CompositeOp::SourceOut,
+        CompositeOp::SourceAtop,
+        CompositeOp::DestinationOver,
+        CompositeOp::DestinationIn,
+        CompositeOp::DestinationOut,
+        CompositeOp::DestinationAtop,
+        CompositeOp::Xor,
+        CompositeOp::PlusLighter,
+    ];
+
+    /// Get the kebab-case name of a compositing operator, as used by CSS `mask-composite`
+    /// and canvas `globalCompositeOperation`.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`CompositeOp`) - The operator to name.
+    ///
+    /// # Returns
+    ///
+    /// - `&'static str` - The name, like `"destination-out"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::CompositeOp;
+    ///
+    /// let name = CompositeOp::DestinationOut.as_str();
+    /// ```
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        use CompositeOp::*;
+
+        match self {
+            Clear => "clear",
+            Copy => "copy",
+            SourceOver => "source-over",
+            SourceIn => "source-in",
+            SourceOut => "source-out",
+            SourceAtop => "source-atop",
+            DestinationOver => "destination-over",
+            DestinationIn => "destination-in",
+            DestinationOut => "destination-out",
+            DestinationAtop => "destination-atop",
+            Xor => "xor",
+            PlusLighter => "plus-lighter",
+        }
+    }
+
+    /// The Porter-Duff fractions `(Fa, Fb)` of the source and backdrop that survive.
+    ///
+    /// `PlusLighter` isn't a true Porter-Duff operator; it uses `(1, 1)` and the caller clamps.
+    #[inline]
+    const fn factors(self, sa: ColorFloat, da: ColorFloat) -> (ColorFloat, ColorFloat) {
+        use CompositeOp::*;
+
+        match self {
+            Clear => (0.0, 0.0),
+            Copy => (1.0, 0.0),
+            SourceOver => (1.0, 1.0 - sa),
+            SourceIn => (da, 0.0),
+            SourceOut => (1.0 - da, 0.0),
+            SourceAtop => (da, 1.0 - sa),
+            DestinationOver => (1.0 - da, 1.0),
+            DestinationIn => (0.0, sa),
+            DestinationOut => (0.0, 1.0 - sa),
+            DestinationAtop => (1.0 - da, sa),
+            Xor => (1.0 - da, 1.0 - sa),
+            PlusLighter => (1.0, 1.0),
+        }
+    }
+}
+
+/// The error returned when a string isn't a compositing operator name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseCompositeOpError;
+
+impl fmt::Display for ParseCompositeOpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown compositing operator")
+    }
+}
+#[cfg(feature = "std")]
+impl core::error::Error for ParseCompositeOpError {}
+
+impl core::str::FromStr for CompositeOp {
+    type Err = ParseCompositeOpError;
+
+    /// Parse a kebab-case compositing operator name (ASCII case is ignored).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CompositeOp::ALL
+            .into_iter()
+            .find(|op| op.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or(ParseCompositeOpError)
+    }
+}
+
+impl fmt::Display for CompositeOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// CIELAB constants. source: https://www.w3.org/TR/css-color-4/#color-conversion-code
+/// Below this OKLCH chroma a color is treated as a gray.
+pub(crate) const GRAY_CHROMA: ColorFloat = 1e-4;
+
+const LAB_KAPPA: ColorFloat = 24389.0 / 27.0;
+const LAB_EPSILON: ColorFloat = 216.0 / 24389.0;
+/// D50 reference white, in XYZ.
+const D50_WHITE: [ColorFloat; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+/// D65 reference white, in XYZ.
+const D65_WHITE: [ColorFloat; 3] = [0.3127 / 0.3290, 1.0, (1.0 - 0.3127 - 0.3290) / 0.3290];
+
 /// A representation of a color in sRGB u8.
 ///
 /// # Fields
@@ -116,7 +379,6 @@ pub enum BlendMode {
 /// - `g` (`u8`) - The green value.
 /// - `b` (`u8`) - The blue value.
 /// - `a` (`u8`) - The alpha value.
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Color {
     r: u8,
@@ -182,7 +444,7 @@ impl Color {
     ///
     /// let dark_gray = Color::new(169, 169, 169, 255);
     /// let steel_blue = Color::new(70, 130, 180, 255);
-    /// let interpolated = dark_gray.lerp(steel_blue);
+    /// let interpolated = dark_gray.lerp(steel_blue, 0.5);
     /// ```
     #[must_use]
     #[inline]
@@ -222,7 +484,7 @@ impl Color {
     ///
     /// let dark_slate_gray = Color::new(47, 79, 79, 255);
     /// let misty_rose = Color::new(255, 228, 225, 255);
-    /// let interpolated = dark_slate_gray.lerp_linear(misty_rose);
+    /// let interpolated = dark_slate_gray.lerp_linear(misty_rose, 0.5);
     /// ```
     #[must_use]
     #[inline]
@@ -260,7 +522,7 @@ impl Color {
     ///
     /// let magenta = Color::new(255, 0, 255, 255);
     /// let green_yellow = Color::new(173, 255, 47, 255);
-    /// let interpolated = magenta.lerp_oklch(green_yellow);
+    /// let interpolated = magenta.lerp_oklch(green_yellow, 0.5);
     /// ```
     #[must_use]
     #[inline]
@@ -389,7 +651,7 @@ impl Color {
         let [sr, sg, sb, sa] = self.into_linear();
         let [dr, dg, db, da] = bg.into_linear();
 
-        let [br, bg_, bb] = Self::blend_channel(mode, [sr, sg, sb], [dr, dg, db]);
+        let [br, bg_, bb] = Self::blend_channel(mode, [dr, dg, db], [sr, sg, sb]);
 
         // Porter–Duff combination in premultiplied form
         let a_out = sa + da - sa * da;
@@ -408,6 +670,70 @@ impl Color {
         Self::from_linear([cr, cg, cb, ca])
     }
 
+    /// Composite a color with a backdrop using a Porter-Duff operator and a blend mode.
+    ///
+    /// The blend mode mixes the colors where both layers are present, then the operator
+    /// decides how much of each layer survives, as described by
+    /// https://www.w3.org/TR/compositing-1/#generalformula.
+    /// `CompositeOp::SourceOver` gives exactly the same result as `Color::blend_over`.
+    ///
+    /// The math is calculated in linear space.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The source color.
+    /// - `bg` (`Color`) - The backdrop color.
+    /// - `op` (`CompositeOp`) - The compositing operator to use.
+    /// - `mode` (`BlendMode`) - The blend mode to use.
+    ///
+    /// # Returns
+    ///
+    /// - `Color` - The composited color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::{BlendMode, Color, CompositeOp};
+    ///
+    /// // punch a half-strength hole into an opaque backdrop
+    /// let mask = Color::new(0, 0, 0, 128);
+    /// let cut = mask.composite(Color::WHITE, CompositeOp::DestinationOut, BlendMode::Normal);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn composite(self, bg: Color, op: CompositeOp, mode: BlendMode) -> Color {
+        if matches!(op, CompositeOp::SourceOver) {
+            return self.blend_over(bg, mode);
+        }
+
+        let [sr, sg, sb, sa] = self.into_linear();
+        let [dr, dg, db, da] = bg.into_linear();
+
+        // where the backdrop is present the source is replaced by the blend result:
+        // Cs' = (1 - ab) * Cs + ab * B(Cb, Cs)
+        let [sr, sg, sb] = if matches!(mode, BlendMode::Normal) {
+            [sr, sg, sb]
+        } else {
+            let [br, bg_, bb] = Self::blend_channel(mode, [dr, dg, db], [sr, sg, sb]);
+            [
+                (1.0 - da) * sr + da * br,
+                (1.0 - da) * sg + da * bg_,
+                (1.0 - da) * sb + da * bb,
+            ]
+        };
+
+        // co = as * Fa * Cs' + ab * Fb * Cb, clamped for plus-lighter
+        let (fa, fb) = op.factors(sa, da);
+        let (ka, kb) = (sa * fa, da * fb);
+        let a_out = (ka + kb).min(1.0);
+        if a_out <= 0.0 {
+            return Color::TRANSPARENT;
+        }
+        let channel = |s: ColorFloat, d: ColorFloat| (ka * s + kb * d).min(1.0) / a_out;
+
+        Self::from_linear([channel(sr, dr), channel(sg, dg), channel(sb, db), a_out])
+    }
+
     /// A faster but slightly less accurate Porter-Duff over in sRGB space.
     ///
     /// For a slower but more accurate result, use `Color::over`.
@@ -594,7 +920,7 @@ impl Color {
     /// use codimate::color::Color;
     ///
     /// let navy = Color::new(0, 0, 128, 255);
-    /// let lightened = navy.lighten_linear();
+    /// let lightened = navy.lighten_linear(0.1);
     /// ```
     #[must_use]
     #[inline]
@@ -623,7 +949,7 @@ impl Color {
     /// use codimate::color::Color;
     ///
     /// let lemon_chiffon = Color::new(255, 250, 205, 255);
-    /// let darkened = lemon_chiffon.darken_linear();
+    /// let darkened = lemon_chiffon.darken_linear(0.1);
     /// ```
     #[must_use]
     #[inline]
@@ -665,6 +991,36 @@ impl Color {
         }
     }
 
+    /// Copy a color but with its alpha multiplied by an opacity.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to fade.
+    /// - `opacity` (`ColorFloat`) - The factor to multiply alpha by.
+    ///   This value will be clamped between and including 0.0 and 1.0.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The color with the scaled alpha.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let olive = Color::new(128, 128, 0, 200);
+    /// let faded_olive = olive.scale_alpha(0.5);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn scale_alpha(self, opacity: ColorFloat) -> Self {
+        if opacity >= 1.0 {
+            return self;
+        }
+        let a = (self.a as ColorFloat * opacity.clamp(0.0, 1.0) + 0.5).floor() as u8;
+        self.with_alpha(a)
+    }
+
     /// Create a color from an RGB array. The alpha defaults to 255.
     ///
     /// # Arguments
@@ -769,6 +1125,58 @@ impl Color {
         [self.r, self.g, self.b, self.a]
     }
 
+    /// Pack a color into a single RGBA8888 pixel word.
+    ///
+    /// The word's bytes in memory are R, G, B, A on every platform, so a slice
+    /// of packed words viewed as bytes is plain RGBA8888. Its numeric value
+    /// depends on the platform's endianness.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to pack.
+    ///
+    /// # Returns
+    ///
+    /// - `u32` - The packed pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let coral = Color::new(255, 127, 80, 255);
+    /// assert_eq!(coral.pack_rgba().to_ne_bytes(), [255, 127, 80, 255]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn pack_rgba(self) -> u32 {
+        u32::from_ne_bytes(self.into_rgba())
+    }
+
+    /// Unpack a color from an RGBA8888 pixel word made by `Color::pack_rgba`.
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`u32`) - The packed pixel.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The unpacked color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let orchid = Color::new(218, 112, 214, 255);
+    /// assert_eq!(Color::unpack_rgba(orchid.pack_rgba()), orchid);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn unpack_rgba(word: u32) -> Self {
+        Self::from_rgba(word.to_ne_bytes())
+    }
+
     /// Get a 6 character hex representation of a color (#RRGGBB).
     ///
     /// # Arguments
@@ -791,7 +1199,7 @@ impl Color {
     #[inline]
     #[cfg(feature = "alloc")]
     pub fn into_hex6(self) -> alloc::string::String {
-        format!("{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        alloc::format!("{:02x}{:02x}{:02x}", self.r, self.g, self.b)
     }
 
     /// Get an 8 character hex representation of a color (#RRGGBBAA).
@@ -816,7 +1224,7 @@ impl Color {
     #[inline]
     #[cfg(feature = "alloc")]
     pub fn into_hex8(self) -> alloc::string::String {
-        format!("{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        alloc::format!("{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
     }
 
     /// Create a color from an HSL array.
@@ -833,7 +1241,7 @@ impl Color {
     /// ```
     /// use codimate::color::Color;
     ///
-    /// let light_salmon = Color::from_hsl([17.143, 100.0, 73.922])
+    /// let light_salmon = Color::from_hsl([17.143, 100.0, 73.922]);
     /// ```
     #[must_use]
     #[inline]
@@ -866,7 +1274,8 @@ impl Color {
     ///
     /// # Arguments
     ///
-    /// - `hsl` (`[ColorFloat; 3]`) - The HSL array.
+    /// - `hsla` (`[ColorFloat; 4]`) - The HSLA array (hue in degrees, saturation and
+    ///   lightness in 0.0-100.0 like [`Color::from_hsl`], alpha in 0.0-1.0).
     ///
     /// # Returns
     ///
@@ -885,8 +1294,8 @@ impl Color {
         // solution from https://www.rapidtables.com/convert/color/hsl-to-rgb.html
         let (h, s, l) = (
             hsla[0].rem_euclid(360.0),
-            hsla[1].clamp(0.0, 1.0),
-            hsla[2].clamp(0.0, 1.0),
+            (hsla[1] / 100.0).clamp(0.0, 1.0),
+            (hsla[2] / 100.0).clamp(0.0, 1.0),
         );
 
         let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
@@ -980,7 +1389,7 @@ impl Color {
     /// use codimate::color::Color;
     ///
     /// let translucent_light_goldenrod_yellow = Color::new(250, 250, 210, 128);
-    /// let [h, s, l, a] = into_hsla(translucent_light_goldenrod_yellow);
+    /// let [h, s, l, a] = translucent_light_goldenrod_yellow.into_hsla();
     /// ```
     #[must_use]
     #[inline]
@@ -1018,145 +1427,142 @@ impl Color {
         [h, s * 100.0, l * 100.0, (self.a as ColorFloat) / 255.0]
     }
 
-    /// Create an encoded sRGB color from linear space (D65, IEC 61966-2-1).
+    /// Create a color from an HSV (also called HSB) array.
     ///
     /// # Arguments
     ///
-    /// - `lin` (`[ColorFloat; 4]`) - The linear RGB array.
+    /// - `hsv` (`[ColorFloat; 3]`) - The HSV array (hue in degrees, saturation and
+    ///   value in 0.0-100.0).
     ///
     /// # Returns
     ///
-    /// - `Self` - The new color.
+    /// - `Self` - The color with the given HSV value.
     ///
     /// # Examples
     ///
     /// ```
     /// use codimate::color::Color;
     ///
-    /// let light_yellow = Color::from_linear([1.0, 1.0, 0.745404]);
+    /// let dark_orange = Color::from_hsv([32.94, 100.0, 100.0]);
     /// ```
     #[must_use]
     #[inline]
-    pub fn from_linear(lin: [ColorFloat; 4]) -> Self {
+    pub fn from_hsv(hsv: [ColorFloat; 3]) -> Self {
+        // solution from https://www.rapidtables.com/convert/color/hsv-to-rgb.html
+        let (h, s, v) = (
+            hsv[0].rem_euclid(360.0),
+            (hsv[1] / 100.0).clamp(0.0, 1.0),
+            (hsv[2] / 100.0).clamp(0.0, 1.0),
+        );
+
+        let c = v * s;
+        let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+        let m = v - c;
+
+        let (r_prime, g_prime, b_prime) = match h {
+            0.0..60.0 => (c, x, 0.0),
+            60.0..120.0 => (x, c, 0.0),
+            120.0..180.0 => (0.0, c, x),
+            180.0..240.0 => (0.0, x, c),
+            240.0..300.0 => (x, 0.0, c),
+            _ => (c, 0.0, x), // 300.0..360.0
+        };
+
         Self {
-            r: Self::encode_srgb(lin[0]),
-            g: Self::encode_srgb(lin[1]),
-            b: Self::encode_srgb(lin[2]),
-            a: {
-                let a = lin[3].clamp(0.0, 1.0);
-                (a * 255.0 + 0.5).floor() as u8
-            },
+            r: ((r_prime + m) * 255.0 + 0.5).floor() as u8,
+            g: ((g_prime + m) * 255.0 + 0.5).floor() as u8,
+            b: ((b_prime + m) * 255.0 + 0.5).floor() as u8,
+            a: 255,
         }
     }
 
-    /// Decode an sRGB color into linear space (D65, IEC 61966-2-1).
+    /// Create a color from an HSVA array.
     ///
     /// # Arguments
     ///
-    /// - `self` (`Color`) - The color to decode to linear space.
+    /// - `hsva` (`[ColorFloat; 4]`) - The HSVA array (hue in degrees, saturation and
+    ///   value in 0.0-100.0, alpha in 0.0-1.0).
     ///
     /// # Returns
     ///
-    /// - `[ColorFloat; 4]` - The array of linear values.
+    /// - `Self` - The color with the given HSVA value.
     ///
     /// # Examples
     ///
     /// ```
     /// use codimate::color::Color;
     ///
-    /// let light_salmon = Color::new(255, 160, 122, 255);
-    /// let [lr, lg, lb, la] = light_salmon.into_linear();
+    /// let translucent_orchid = Color::from_hsva([302.26, 48.62, 85.49, 0.5]);
     /// ```
     #[must_use]
     #[inline]
-    pub fn into_linear(self) -> [ColorFloat; 4] {
-        [
-            Self::decode_srgb(self.r),
-            Self::decode_srgb(self.g),
-            Self::decode_srgb(self.b),
-            (self.a as f64 / 255.0) as ColorFloat,
-        ]
+    pub fn from_hsva(hsva: [ColorFloat; 4]) -> Self {
+        Self::from_hsv([hsva[0], hsva[1], hsva[2]]).with_alpha(Self::alpha_to_u8(hsva[3]))
     }
 
-    /// Create a color from an OKLAB array.
+    /// Get an HSV (also called HSB) representation of a color.
     ///
     /// # Arguments
     ///
-    /// - `lab` (`[ColorFloat; 3]`) - The OKLAB array.
+    /// - `self` (`Color`) - The color to get the HSV representation of.
     ///
     /// # Returns
     ///
-    /// - `Self` - The new color.
+    /// - `[ColorFloat; 3]` - The HSV representation (hue in degrees, saturation
+    ///   and value in 0.0-100.0).
     ///
     /// # Examples
     ///
     /// ```
     /// use codimate::color::Color;
     ///
-    /// let yellow_green = Color::from_oklab([0.784852, -0.109642, 0.147442]);
+    /// let steel_blue = Color::new(70, 130, 180, 255);
+    /// let [h, s, v] = steel_blue.into_hsv();
     /// ```
     #[must_use]
     #[inline]
-    pub fn from_oklab(lab: [ColorFloat; 3]) -> Self {
-        // source: https://bottosson.github.io/posts/oklab/
-
-        let l_ = lab[0] + 0.39633778 * lab[1] + 0.21580376 * lab[2];
-        let m_ = lab[0] - 0.105561346 * lab[1] - 0.06385417 * lab[2];
-        let s_ = lab[0] - 0.08948418 * lab[1] - 1.2914856 * lab[2];
-
-        let l = l_ * l_ * l_;
-        let m = m_ * m_ * m_;
-        let s = s_ * s_ * s_;
+    pub fn into_hsv(self) -> [ColorFloat; 3] {
+        // solution from https://www.rapidtables.com/convert/color/rgb-to-hsv.html
+        let (h, c_max, c_min) = self.hue_max_min();
+        let s = if c_max == 0.0 {
+            0.0
+        } else {
+            (c_max - c_min) / c_max
+        };
 
-        Self::from_linear([
-            4.0767417 * l - 3.3077116 * m + 0.23096993 * s,
-            -1.268438 * l + 2.6097574 * m - 0.3413194 * s,
-            -0.0041960863 * l - 0.7034186 * m + 1.7076147 * s,
-            1.0,
-        ])
+        [h, s * 100.0, c_max * 100.0]
     }
 
-    /// Get an OKLAB representation of a color.
+    /// Get an HSVA representation of a color.
     ///
     /// # Arguments
     ///
-    /// - `self` (`Color`) - The color to get the OKLAB representation of.
+    /// - `self` (`Color`) - The color to get the HSVA representation of.
     ///
     /// # Returns
     ///
-    /// - `[ColorFloat; 3]` - The OKLAB representation.
+    /// - `[ColorFloat; 4]` - The HSVA representation (alpha in 0.0-1.0).
     ///
     /// # Examples
     ///
     /// ```
     /// use codimate::color::Color;
     ///
-    /// let navajo_white = Color::new(255, 222, 173, 255);
-    /// let [l, a, b] = navajo_white.into_oklab();
+    /// let translucent_plum = Color::new(221, 160, 221, 64);
+    /// let [h, s, v, a] = translucent_plum.into_hsva();
     /// ```
     #[must_use]
     #[inline]
-    pub fn into_oklab(self) -> [ColorFloat; 3] {
-        // source: https://bottosson.github.io/posts/oklab/
-
-        let lin = self.into_linear();
-
-        let l = (0.41222147 * lin[0] + 0.53633254 * lin[1] + 0.051445993 * lin[2]).cbrt();
-        let m = (0.2119035 * lin[0] + 0.6806996 * lin[1] + 0.10739696 * lin[2]).cbrt();
-        let s = (0.08830246 * lin[0] + 0.28171884 * lin[1] + 0.6299787 * lin[2]).cbrt();
-
-        [
-            0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
-            1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
-            0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
-        ]
+    pub fn into_hsva(self) -> [ColorFloat; 4] {
+        let [h, s, v] = self.into_hsv();
+        [h, s, v, (self.a as ColorFloat) / 255.0]
     }
 
-    /// Create a color from an OKLCH array.
+    /// Create an encoded sRGB color from linear space (D65, IEC 61966-2-1).
     ///
     /// # Arguments
     ///
-    /// - `lch` (`[ColorFloat; 3]`) - The OKLCH array.
+    /// - `lin` (`[ColorFloat; 4]`) - The linear RGB array.
     ///
     /// # Returns
     ///
@@ -1167,84 +1573,942 @@ impl Color {
     /// ```
     /// use codimate::color::Color;
     ///
-    /// let peru = Color::from_oklch([0.678193, 0.122749, 62.181584]);
+    /// let light_yellow = Color::from_linear([1.0, 1.0, 0.745404, 1.0]);
     /// ```
     #[must_use]
     #[inline]
-    pub fn from_oklch(lch: [ColorFloat; 3]) -> Self {
-        // Gamut mapping to keep rgb valid when converting
-        // current method: chroma reduction at fixed L and H
-        // switch to Björn Ottosson's "gamut mapping in OKLCH"
-        // in the future if perfect ramping needed
-        let within = |rgb: [ColorFloat; 3]| {
-            rgb[0] >= 0.0
-                && rgb[0] <= 1.0
-                && rgb[1] >= 0.0
-                && rgb[1] <= 1.0
-                && rgb[2] >= 0.0
-                && rgb[2] <= 1.0
-        };
-        let to_srgb = |lch: [ColorFloat; 3]| {
-            let lin = Self::from_oklab(Self::oklch_to_oklab(lch)).into_linear();
-            [lin[0], lin[1], lin[2]]
-        };
+    pub fn from_linear(lin: [ColorFloat; 4]) -> Self {
+        Self {
+            r: Self::encode_srgb(lin[0]),
+            g: Self::encode_srgb(lin[1]),
+            b: Self::encode_srgb(lin[2]),
+            a: {
+                let a = lin[3].clamp(0.0, 1.0);
+                (a * 255.0 + 0.5).floor() as u8
+            },
+        }
+    }
+
+    /// Decode an sRGB color into linear space (D65, IEC 61966-2-1).
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to decode to linear space.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 4]` - The array of linear values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let light_salmon = Color::new(255, 160, 122, 255);
+    /// let [lr, lg, lb, la] = light_salmon.into_linear();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_linear(self) -> [ColorFloat; 4] {
+        [
+            Self::decode_srgb(self.r),
+            Self::decode_srgb(self.g),
+            Self::decode_srgb(self.b),
+            (self.a as f64 / 255.0) as ColorFloat,
+        ]
+    }
+
+    /// Create a color from an OKLAB array.
+    ///
+    /// Colors outside of the sRGB gamut are brought into it with the CSS Color 4
+    /// gamut mapping algorithm (chroma reduction in OKLCH), which keeps the hue.
+    ///
+    /// # Arguments
+    ///
+    /// - `lab` (`[ColorFloat; 3]`) - The OKLAB array.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let yellow_green = Color::from_oklab([0.784852, -0.109642, 0.147442]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_oklab(lab: [ColorFloat; 3]) -> Self {
+        let lin = Self::oklab_to_linear(lab);
+        let [r, g, b] = if gamut::in_srgb_gamut(lin) {
+            lin
+        } else {
+            gamut::gamut_map_oklch(Self::oklab_to_oklch(lab))
+        };
+        Self::from_linear([r, g, b, 1.0])
+    }
+
+    /// Get an OKLAB representation of a color.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to get the OKLAB representation of.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The OKLAB representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let navajo_white = Color::new(255, 222, 173, 255);
+    /// let [l, a, b] = navajo_white.into_oklab();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_oklab(self) -> [ColorFloat; 3] {
+        let [r, g, b, _] = self.into_linear();
+        Self::linear_to_oklab([r, g, b])
+    }
+
+    /// Create a color from an OKLCH array.
+    ///
+    /// Colors outside of the sRGB gamut are brought into it with the CSS Color 4
+    /// gamut mapping algorithm, which lowers chroma at a fixed lightness and hue.
+    ///
+    /// # Arguments
+    ///
+    /// - `lch` (`[ColorFloat; 3]`) - The OKLCH array.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let peru = Color::from_oklch([0.678193, 0.122749, 62.181584]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_oklch(lch: [ColorFloat; 3]) -> Self {
+        let [r, g, b] = gamut::gamut_map_oklch(lch);
+        Self::from_linear([r, g, b, 1.0])
+    }
+
+    /// `from_oklch` that keeps grays neutral.
+    ///
+    /// Grays come back from `into_oklch` with a hair of chroma and a noisy hue, and
+    /// converting them back can round one channel differently from the others. Below
+    /// `GRAY_CHROMA` the color is built from a single channel instead, so it stays gray.
+    #[must_use]
+    #[inline]
+    pub(crate) fn from_oklch_neutral(lch: [ColorFloat; 3]) -> Self {
+        let [l, c, _] = lch;
+        if c < GRAY_CHROMA {
+            let [v, ..] = Self::from_oklch([l, 0.0, 0.0]).into_rgba();
+            Self::new(v, v, v, 255)
+        } else {
+            Self::from_oklch(lch)
+        }
+    }
+
+    /// Get an OKLCH representation of a color.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to get an OKLCH representation of.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The OKLCH representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let floral_white = Color::new(255, 250, 240, 255);
+    /// let [l, c, h] = floral_white.into_oklch();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_oklch(self) -> [ColorFloat; 3] {
+        Self::oklab_to_oklch(self.into_oklab())
+    }
+
+    /// Create a color from an HWB array.
+    ///
+    /// # Arguments
+    ///
+    /// - `hwb` (`[ColorFloat; 3]`) - The HWB array (hue in degrees, whiteness and
+    ///   blackness in 0.0-100.0). If whiteness and blackness add up to 100 or more,
+    ///   they are normalized and the result is a gray.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The color with the given HWB value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let medium_sea_green = Color::from_hwb([146.72, 23.14, 29.8]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_hwb(hwb: [ColorFloat; 3]) -> Self {
+        // source: https://www.w3.org/TR/css-color-4/#hwb-to-rgb
+        let h = hwb[0].rem_euclid(360.0);
+        let w = hwb[1].max(0.0) / 100.0;
+        let b = hwb[2].max(0.0) / 100.0;
+
+        if w + b >= 1.0 {
+            let gray = w / (w + b);
+            let v = (gray * 255.0 + 0.5).floor() as u8;
+            return Self::new(v, v, v, 255);
+        }
+
+        // fully saturated hue at 50% lightness, then mix in white and black
+        let x = 1.0 - ((h / 60.0) % 2.0 - 1.0).abs();
+        let (r, g, bl) = match h {
+            0.0..60.0 => (1.0, x, 0.0),
+            60.0..120.0 => (x, 1.0, 0.0),
+            120.0..180.0 => (0.0, 1.0, x),
+            180.0..240.0 => (0.0, x, 1.0),
+            240.0..300.0 => (x, 0.0, 1.0),
+            _ => (1.0, 0.0, x), // 300.0..360.0
+        };
+
+        let scale = 1.0 - w - b;
+        let channel = |c: ColorFloat| ((c * scale + w) * 255.0 + 0.5).floor() as u8;
+
+        Self::new(channel(r), channel(g), channel(bl), 255)
+    }
+
+    /// Create a color from an HWBA array.
+    ///
+    /// # Arguments
+    ///
+    /// - `hwba` (`[ColorFloat; 4]`) - The HWBA array (hue in degrees, whiteness and
+    ///   blackness in 0.0-100.0, alpha in 0.0-1.0).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The color with the given HWBA value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_tomato = Color::from_hwba([9.13, 27.84, 0.0, 0.75]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_hwba(hwba: [ColorFloat; 4]) -> Self {
+        Self::from_hwb([hwba[0], hwba[1], hwba[2]]).with_alpha(Self::alpha_to_u8(hwba[3]))
+    }
+
+    /// Get an HWB representation of a color.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to get the HWB representation of.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The HWB representation (hue in degrees, whiteness
+    ///   and blackness in 0.0-100.0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let khaki = Color::new(240, 230, 140, 255);
+    /// let [h, w, b] = khaki.into_hwb();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_hwb(self) -> [ColorFloat; 3] {
+        // source: https://www.w3.org/TR/css-color-4/#rgb-to-hwb
+        let (h, c_max, c_min) = self.hue_max_min();
+        [h, c_min * 100.0, (1.0 - c_max) * 100.0]
+    }
+
+    /// Get an HWBA representation of a color.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to get the HWBA representation of.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 4]` - The HWBA representation (alpha in 0.0-1.0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_thistle = Color::new(216, 191, 216, 200);
+    /// let [h, w, b, a] = translucent_thistle.into_hwba();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_hwba(self) -> [ColorFloat; 4] {
+        let [h, w, b] = self.into_hwb();
+        [h, w, b, (self.a as ColorFloat) / 255.0]
+    }
+
+    /// Create a color from a CMYK array.
+    ///
+    /// This is the naive conversion with no ink profile, so it won't match
+    /// what a printer produces. It's meant for round trips and rough previews.
+    ///
+    /// # Arguments
+    ///
+    /// - `cmyk` (`[ColorFloat; 4]`) - The CMYK array (every component in 0.0-100.0).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The color with the given CMYK value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let dark_cyan = Color::from_cmyk([100.0, 0.0, 0.0, 45.49]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_cmyk(cmyk: [ColorFloat; 4]) -> Self {
+        // solution from https://www.rapidtables.com/convert/color/cmyk-to-rgb.html
+        let k = (cmyk[3] / 100.0).clamp(0.0, 1.0);
+        let channel = |c: ColorFloat| {
+            let c = (c / 100.0).clamp(0.0, 1.0);
+            ((1.0 - c) * (1.0 - k) * 255.0 + 0.5).floor() as u8
+        };
+
+        Self::new(channel(cmyk[0]), channel(cmyk[1]), channel(cmyk[2]), 255)
+    }
+
+    /// Create a color from a CMYKA array.
+    ///
+    /// # Arguments
+    ///
+    /// - `cmyka` (`[ColorFloat; 5]`) - The CMYKA array (cyan, magenta, yellow and
+    ///   black in 0.0-100.0, alpha in 0.0-1.0).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The color with the given CMYKA value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_gold = Color::from_cmyka([0.0, 15.69, 100.0, 0.0, 0.5]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_cmyka(cmyka: [ColorFloat; 5]) -> Self {
+        Self::from_cmyk([cmyka[0], cmyka[1], cmyka[2], cmyka[3]])
+            .with_alpha(Self::alpha_to_u8(cmyka[4]))
+    }
+
+    /// Get a CMYK representation of a color.
+    ///
+    /// This is the naive conversion with no ink profile; black is pulled out
+    /// as fully as possible, so grays come out as pure `k`.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to get the CMYK representation of.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 4]` - The CMYK representation (every component in 0.0-100.0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let indigo = Color::new(75, 0, 130, 255);
+    /// let [c, m, y, k] = indigo.into_cmyk();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_cmyk(self) -> [ColorFloat; 4] {
+        // solution from https://www.rapidtables.com/convert/color/rgb-to-cmyk.html
+        let r_prime = (self.r as ColorFloat) / 255.0;
+        let g_prime = (self.g as ColorFloat) / 255.0;
+        let b_prime = (self.b as ColorFloat) / 255.0;
+
+        let c_max = r_prime.max(g_prime).max(b_prime);
+        let k = 1.0 - c_max;
+        if c_max == 0.0 {
+            // black: cyan, magenta and yellow are undefined
+            return [0.0, 0.0, 0.0, 100.0];
+        }
+
+        let ink = |v: ColorFloat| (c_max - v) / c_max * 100.0;
+        [ink(r_prime), ink(g_prime), ink(b_prime), k * 100.0]
+    }
+
+    /// Get a CMYKA representation of a color.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to get the CMYKA representation of.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 5]` - The CMYKA representation (alpha in 0.0-1.0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_crimson = Color::new(220, 20, 60, 128);
+    /// let [c, m, y, k, a] = translucent_crimson.into_cmyka();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_cmyka(self) -> [ColorFloat; 5] {
+        let [c, m, y, k] = self.into_cmyk();
+        [c, m, y, k, (self.a as ColorFloat) / 255.0]
+    }
+
+    /// Create a color from a CIELAB array (D50 white point, as used by CSS).
+    ///
+    /// Colors outside of the sRGB gamut are gamut mapped like `Color::from_oklch`.
+    ///
+    /// # Arguments
+    ///
+    /// - `lab` (`[ColorFloat; 3]`) - The CIELAB array (L in 0.0-100.0).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let teal = Color::from_lab([47.99, -30.39, -8.98]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_lab(lab: [ColorFloat; 3]) -> Self {
+        Self::from_xyz_d50(Self::lab_to_xyz(lab, D50_WHITE))
+    }
+
+    /// Convert a color into a CIELAB array (D50 white point, as used by CSS).
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The CIELAB array (L in 0.0-100.0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let olive_drab = Color::new(107, 142, 35, 255);
+    /// let [l, a, b] = olive_drab.into_lab();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_lab(self) -> [ColorFloat; 3] {
+        Self::xyz_to_lab(self.into_xyz_d50(), D50_WHITE)
+    }
+
+    /// Create a color from a CIE LCh(ab) array (D50 white point, as used by CSS).
+    ///
+    /// Colors outside of the sRGB gamut are gamut mapped like `Color::from_oklch`.
+    ///
+    /// # Arguments
+    ///
+    /// - `lch` (`[ColorFloat; 3]`) - The LCh array (L in 0.0-100.0, hue in degrees).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let sienna = Color::from_lch([43.8, 47.47, 54.88]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_lch(lch: [ColorFloat; 3]) -> Self {
+        // same polar -> rectangular step as OKLCH -> OKLAB
+        Self::from_lab(Self::oklch_to_oklab(lch))
+    }
+
+    /// Get a CIE LCh(ab) representation of a color (D50 white point, as used by CSS).
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The LCh array (L in 0.0-100.0, hue in 0.0..360.0 degrees).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let dark_khaki = Color::new(189, 183, 107, 255);
+    /// let [l, c, h] = dark_khaki.into_lch();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_lch(self) -> [ColorFloat; 3] {
+        Self::oklab_to_oklch(self.into_lab())
+    }
+
+    /// Create a color from a CIELAB array with alpha (D50 white point, as used by CSS).
+    ///
+    /// # Arguments
+    ///
+    /// - `laba` (`[ColorFloat; 4]`) - The CIELAB array (L in 0.0-100.0) and alpha (0.0-1.0).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_maroon = Color::from_laba([26.17, 48.47, 39.44, 0.5]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_laba(laba: [ColorFloat; 4]) -> Self {
+        Self::from_lab([laba[0], laba[1], laba[2]]).with_alpha(Self::alpha_to_u8(laba[3]))
+    }
+
+    /// Convert a color into a CIELAB array with alpha (D50 white point, as used by CSS).
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 4]` - The CIELAB array (L in 0.0-100.0) and alpha (0.0-1.0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_orchid = Color::new(218, 112, 214, 128);
+    /// let [l, a, b, alpha] = translucent_orchid.into_laba();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_laba(self) -> [ColorFloat; 4] {
+        let [l, a, b] = self.into_lab();
+        [l, a, b, (self.a as ColorFloat) / 255.0]
+    }
+
+    /// Create a color from a CIE LCh(ab) array with alpha (D50 white point, as used by CSS).
+    ///
+    /// # Arguments
+    ///
+    /// - `lcha` (`[ColorFloat; 4]`) - The LCh array (L in 0.0-100.0, hue in degrees)
+    ///   and alpha (0.0-1.0).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_sienna = Color::from_lcha([43.8, 47.47, 54.88, 0.25]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_lcha(lcha: [ColorFloat; 4]) -> Self {
+        Self::from_lch([lcha[0], lcha[1], lcha[2]]).with_alpha(Self::alpha_to_u8(lcha[3]))
+    }
+
+    /// Get a CIE LCh(ab) representation of a color with alpha (D50 white point, as used by CSS).
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 4]` - The LCh array (L in 0.0-100.0, hue in 0.0..360.0 degrees)
+    ///   and alpha (0.0-1.0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_peru = Color::new(205, 133, 63, 100);
+    /// let [l, c, h, a] = translucent_peru.into_lcha();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_lcha(self) -> [ColorFloat; 4] {
+        let [l, c, h] = self.into_lch();
+        [l, c, h, (self.a as ColorFloat) / 255.0]
+    }
+
+    /// Create a color from a CIELAB array with a D65 white point.
+    ///
+    /// CSS uses D50 (see `Color::from_lab`); D65 Lab is what most image tools and
+    /// textbook formulas assume for sRGB content. Colors outside of the sRGB gamut
+    /// are gamut mapped like `Color::from_oklch`.
+    ///
+    /// # Arguments
+    ///
+    /// - `lab` (`[ColorFloat; 3]`) - The CIELAB array (L in 0.0-100.0).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let teal = Color::from_lab_d65([48.25, -28.84, -8.48]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_lab_d65(lab: [ColorFloat; 3]) -> Self {
+        Self::from_xyz_d65(Self::lab_to_xyz(lab, D65_WHITE))
+    }
+
+    /// Convert a color into a CIELAB array with a D65 white point.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The CIELAB array (L in 0.0-100.0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let olive_drab = Color::new(107, 142, 35, 255);
+    /// let [l, a, b] = olive_drab.into_lab_d65();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_lab_d65(self) -> [ColorFloat; 3] {
+        Self::xyz_to_lab(self.into_xyz_d65(), D65_WHITE)
+    }
+
+    /// Create a color from a CIELAB array with alpha and a D65 white point.
+    ///
+    /// # Arguments
+    ///
+    /// - `laba` (`[ColorFloat; 4]`) - The CIELAB array (L in 0.0-100.0) and alpha (0.0-1.0).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_teal = Color::from_laba_d65([48.25, -28.84, -8.48, 0.5]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_laba_d65(laba: [ColorFloat; 4]) -> Self {
+        Self::from_lab_d65([laba[0], laba[1], laba[2]]).with_alpha(Self::alpha_to_u8(laba[3]))
+    }
 
-        if within(to_srgb(lch)) {
-            return Self::from_oklab(Self::oklch_to_oklab(lch));
-        }
+    /// Convert a color into a CIELAB array with alpha and a D65 white point.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 4]` - The CIELAB array (L in 0.0-100.0) and alpha (0.0-1.0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_salmon = Color::new(250, 128, 114, 128);
+    /// let [l, a, b, alpha] = translucent_salmon.into_laba_d65();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_laba_d65(self) -> [ColorFloat; 4] {
+        let [l, a, b] = self.into_lab_d65();
+        [l, a, b, (self.a as ColorFloat) / 255.0]
+    }
 
-        // shrink c
-        let (mut lo, mut hi) = (0.0f32, lch[1]);
-        for _ in 0..24 {
-            // ~1e-7 precision
-            let mid = 0.5 * (lo + hi);
-            let test = [lch[0], mid, lch[2]];
-            if within(to_srgb(test)) {
-                lo = mid;
-            } else {
-                hi = mid;
-            }
-        }
+    /// Create a color from a CIE LCh(ab) array with a D65 white point.
+    ///
+    /// Colors outside of the sRGB gamut are gamut mapped like `Color::from_oklch`.
+    ///
+    /// # Arguments
+    ///
+    /// - `lch` (`[ColorFloat; 3]`) - The LCh array (L in 0.0-100.0, hue in degrees).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let royal_blue = Color::from_lch_d65([47.83, 70.35, 291.93]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_lch_d65(lch: [ColorFloat; 3]) -> Self {
+        Self::from_lab_d65(Self::oklch_to_oklab(lch))
+    }
+
+    /// Get a CIE LCh(ab) representation of a color with a D65 white point.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to convert.
+    ///
+    /// # Returns
+    ///
+    /// - `[ColorFloat; 3]` - The LCh array (L in 0.0-100.0, hue in 0.0..360.0 degrees).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let hot_pink = Color::new(255, 105, 180, 255);
+    /// let [l, c, h] = hot_pink.into_lch_d65();
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn into_lch_d65(self) -> [ColorFloat; 3] {
+        Self::oklab_to_oklch(self.into_lab_d65())
+    }
 
-        Self::from_oklab(Self::oklch_to_oklab([lch[0], lo, lch[2]]))
+    /// Create a color from a CIE LCh(ab) array with alpha and a D65 white point.
+    ///
+    /// # Arguments
+    ///
+    /// - `lcha` (`[ColorFloat; 4]`) - The LCh array (L in 0.0-100.0, hue in degrees)
+    ///   and alpha (0.0-1.0).
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let translucent_royal_blue = Color::from_lcha_d65([47.83, 70.35, 291.93, 0.5]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn from_lcha_d65(lcha: [ColorFloat; 4]) -> Self {
+        Self::from_lch_d65([lcha[0], lcha[1], lcha[2]]).with_alpha(Self::alpha_to_u8(lcha[3]))
     }
 
-    /// Get an OKLCH representation of a color.
+    /// Get a CIE LCh(ab) representation of a color with alpha and a D65 white point.
     ///
     /// # Arguments
     ///
-    /// - `self` (`Color`) - The color to get an OKLCH representation of.
+    /// - `self` (`Color`) - The color to convert.
     ///
     /// # Returns
     ///
-    /// - `[ColorFloat; 3]` - The OKLCH representation.
+    /// - `[ColorFloat; 4]` - The LCh array (L in 0.0-100.0, hue in 0.0..360.0 degrees)
+    ///   and alpha (0.0-1.0).
     ///
     /// # Examples
     ///
     /// ```
     /// use codimate::color::Color;
     ///
-    /// let floral_white = Color::new(255, 250, 240, 255);
-    /// let [l, c, h] = floral_white.into_oklch();
+    /// let translucent_lime = Color::new(0, 255, 0, 64);
+    /// let [l, c, h, a] = translucent_lime.into_lcha_d65();
     /// ```
     #[must_use]
     #[inline]
-    pub fn into_oklch(self) -> [ColorFloat; 3] {
-        let [l, a, b] = self.into_oklab();
-        let c = (a * a + b * b).sqrt();
-        let mut h = b.atan2(a).to_degrees();
-        if h < 0.0 {
-            h += 360.0;
+    pub fn into_lcha_d65(self) -> [ColorFloat; 4] {
+        let [l, c, h] = self.into_lch_d65();
+        [l, c, h, (self.a as ColorFloat) / 255.0]
+    }
+
+    /// Get the CSS name of a color, if it has one.
+    ///
+    /// Only exact matches count, so the color has to be fully opaque
+    /// (or `Color::TRANSPARENT`, which is named `"transparent"`).
+    /// Where CSS has aliases, the first alphabetically is returned
+    /// (`"aqua"` over `"cyan"`, `"gray"` over `"grey"`).
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to name.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&'static str>` - The lowercase CSS name of the color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let rebecca_purple = Color::new(102, 51, 153, 255);
+    /// let name = rebecca_purple.css_name();
+    /// ```
+    #[must_use]
+    pub fn css_name(self) -> Option<&'static str> {
+        if self == Self::TRANSPARENT {
+            return Some("transparent");
         }
-        [l, c, h]
+
+        crate::color::named::NAMED_COLORS
+            .iter()
+            .find(|(_, c)| *c == self)
+            .map(|(name, _)| *name)
     }
 
     // --- private methods --- //
 
+    /// The HSL/HSV/HWB hue of a color (0.0 for grays), with its largest and
+    /// smallest channels in 0.0-1.0.
+    #[must_use]
+    #[inline]
+    fn hue_max_min(self) -> (ColorFloat, ColorFloat, ColorFloat) {
+        let r_prime = (self.r as ColorFloat) / 255.0;
+        let g_prime = (self.g as ColorFloat) / 255.0;
+        let b_prime = (self.b as ColorFloat) / 255.0;
+
+        let c_max = r_prime.max(g_prime).max(b_prime);
+        let c_min = r_prime.min(g_prime).min(b_prime);
+        let delta = c_max - c_min;
+
+        let h = if delta == 0.0 {
+            0.0
+        } else {
+            match c_max {
+                _ if r_prime == c_max => 60.0 * ((g_prime - b_prime) / delta).rem_euclid(6.0),
+                _ if g_prime == c_max => 60.0 * ((b_prime - r_prime) / delta + 2.0),
+                _ => 60.0 * ((r_prime - g_prime) / delta + 4.0), // b_prime == c_max
+            }
+        };
+
+        (h, c_max, c_min)
+    }
+
+    /// Convert a 0.0-1.0 alpha to u8, clamping out of range values.
+    #[must_use]
+    #[inline]
+    fn alpha_to_u8(a: ColorFloat) -> u8 {
+        (a.clamp(0.0, 1.0) * 255.0 + 0.5).floor() as u8
+    }
+
+    /// Convert a CIELAB array to CIE XYZ, both relative to `white`.
+    #[must_use]
+    #[inline]
+    fn lab_to_xyz(lab: [ColorFloat; 3], white: [ColorFloat; 3]) -> [ColorFloat; 3] {
+        // source: https://www.w3.org/TR/css-color-4/#color-conversion-code
+        let [l, a, b] = lab;
+        let fy = (l + 16.0) / 116.0;
+        let fx = fy + a / 500.0;
+        let fz = fy - b / 200.0;
+
+        let xr = if fx * fx * fx > LAB_EPSILON {
+            fx * fx * fx
+        } else {
+            (116.0 * fx - 16.0) / LAB_KAPPA
+        };
+        let yr = if l > LAB_KAPPA * LAB_EPSILON {
+            fy * fy * fy
+        } else {
+            l / LAB_KAPPA
+        };
+        let zr = if fz * fz * fz > LAB_EPSILON {
+            fz * fz * fz
+        } else {
+            (116.0 * fz - 16.0) / LAB_KAPPA
+        };
+
+        [xr * white[0], yr * white[1], zr * white[2]]
+    }
+
+    /// Convert CIE XYZ to a CIELAB array, both relative to `white`.
+    #[must_use]
+    #[inline]
+    fn xyz_to_lab(xyz: [ColorFloat; 3], white: [ColorFloat; 3]) -> [ColorFloat; 3] {
+        // source: https://www.w3.org/TR/css-color-4/#color-conversion-code
+        let f = |v: ColorFloat| {
+            if v > LAB_EPSILON {
+                v.cbrt()
+            } else {
+                (LAB_KAPPA * v + 16.0) / 116.0
+            }
+        };
+        let fx = f(xyz[0] / white[0]);
+        let fy = f(xyz[1] / white[1]);
+        let fz = f(xyz[2] / white[2]);
+
+        [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+    }
+
     /// Convert an OKLCH array to an OKLAB array.
     #[must_use]
     #[inline]
-    fn oklch_to_oklab(lch: [ColorFloat; 3]) -> [ColorFloat; 3] {
+    pub(crate) fn oklch_to_oklab(lch: [ColorFloat; 3]) -> [ColorFloat; 3] {
         let (l, c, h) = (lch[0], lch[1], lch[2]);
         let h = h.to_radians();
         let a = c * h.cos();
@@ -1252,11 +2516,60 @@ impl Color {
         [l, a, b]
     }
 
+    /// Convert an OKLAB array to an OKLCH array (hue in 0.0..360.0 degrees).
+    #[must_use]
+    #[inline]
+    pub(crate) fn oklab_to_oklch(lab: [ColorFloat; 3]) -> [ColorFloat; 3] {
+        let [l, a, b] = lab;
+        let c = (a * a + b * b).sqrt();
+        let mut h = b.atan2(a).to_degrees();
+        if h < 0.0 {
+            h += 360.0;
+        }
+        [l, c, h]
+    }
+
+    /// Convert unclamped linear sRGB to OKLAB.
+    #[must_use]
+    #[inline]
+    pub(crate) fn linear_to_oklab(lin: [ColorFloat; 3]) -> [ColorFloat; 3] {
+        // source: https://bottosson.github.io/posts/oklab/
+        let l = (0.41222147 * lin[0] + 0.53633254 * lin[1] + 0.051445993 * lin[2]).cbrt();
+        let m = (0.2119035 * lin[0] + 0.6806996 * lin[1] + 0.10739696 * lin[2]).cbrt();
+        let s = (0.08830246 * lin[0] + 0.28171884 * lin[1] + 0.6299787 * lin[2]).cbrt();
+
+        [
+            0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+            1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+            0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+        ]
+    }
+
+    /// Convert OKLAB to unclamped linear sRGB.
+    #[must_use]
+    #[inline]
+    pub(crate) fn oklab_to_linear(lab: [ColorFloat; 3]) -> [ColorFloat; 3] {
+        // source: https://bottosson.github.io/posts/oklab/
+        let l_ = lab[0] + 0.39633778 * lab[1] + 0.21580376 * lab[2];
+        let m_ = lab[0] - 0.105561346 * lab[1] - 0.06385417 * lab[2];
+        let s_ = lab[0] - 0.08948418 * lab[1] - 1.2914856 * lab[2];
+
+        let l = l_ * l_ * l_;
+        let m = m_ * m_ * m_;
+        let s = s_ * s_ * s_;
+
+        [
+            4.0767417 * l - 3.3077116 * m + 0.23096993 * s,
+            -1.268438 * l + 2.6097574 * m - 0.3413194 * s,
+            -0.0041960863 * l - 0.7034186 * m + 1.7076147 * s,
+        ]
+    }
+
     /// Decode an 8 bit sRGB value into a linear float using a lookup table.
     #[cfg(feature = "srgb_lut")]
     #[inline]
     fn decode_srgb(srgb_u8: u8) -> ColorFloat {
-        crate::color::lut::decode_srgb_lut_f32(srgb_u8) as ColorFloat
+        crate::color::lut::decode_srgb_lut(srgb_u8)
     }
 
     /// Decode an 8 bit sRGB value into a linear float.
@@ -1275,7 +2588,7 @@ impl Color {
     #[cfg(feature = "srgb_lut")]
     #[inline]
     fn encode_srgb(lin: ColorFloat) -> u8 {
-        crate::color::lut::encode_srgb_lut_f32(lin)
+        crate::color::lut::encode_srgb_lut(lin)
     }
 
     /// Encode a linear float into an 8 bit sRGB value.
@@ -1306,17 +2619,9 @@ impl Color {
         let n = r.min(g).min(b);
         let x = r.max(g).max(b);
         if n < 0.0 {
-            c.iter()
-                .map(|&v| l + (((v - l) * l) / (l - n)))
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap()
+            c.map(|v| l + (((v - l) * l) / (l - n)))
         } else if x > 1.0 {
-            c.iter()
-                .map(|&v| l + (((v - l) * (1.0 - l)) / (x - l)))
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap()
+            c.map(|v| l + (((v - l) * (1.0 - l)) / (x - l)))
         } else {
             c
         }
@@ -1422,17 +2727,12 @@ impl Color {
     }
 
     /// Combine two colors with a blend function.
+    #[inline]
     fn blend<F>(backdrop: &[ColorFloat; 3], source: &[ColorFloat; 3], mut f: F) -> [ColorFloat; 3]
     where
         F: FnMut(ColorFloat, ColorFloat) -> ColorFloat,
     {
-        backdrop
-            .iter()
-            .zip(source)
-            .map(|(&b, &s)| f(b, s))
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap()
+        core::array::from_fn(|i| f(backdrop[i], source[i]))
     }
 
     #[inline]
@@ -1449,7 +2749,8 @@ impl Color {
             Normal => source,
             Multiply => Self::blend(&backdrop, &source, |b, s| b * s),
             Screen => Self::blend(&backdrop, &source, |b, s| b + s - (b * s)),
-            Overlay => Self::blend_channel(HardLight, backdrop, source),
+            // overlay is hard-light with the layers swapped
+            Overlay => Self::blend_channel(HardLight, source, backdrop),
             Darken => Self::blend(&backdrop, &source, |b, s| b.min(s)),
             Lighten => Self::blend(&backdrop, &source, |b, s| b.max(s)),
             ColorDodge => Self::blend(&backdrop, &source, |b, s| {
@@ -1627,3 +2928,243 @@ impl fmt::Display for Color {
 // MDN Web Docs
 
 // WCAG examples: verify contrast of known pairs (e.g., pure black vs white = 21:1)
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: [Color; 6] = [
+        Color::BLACK,
+        Color::WHITE,
+        Color::new(128, 128, 128, 255),
+        Color::new(200, 50, 50, 255),
+        Color::new(30, 144, 255, 255),
+        Color::new(250, 240, 20, 255),
+    ];
+
+    #[test]
+    fn blend_over_uses_bg_as_backdrop() {
+        let gray = Color::new(128, 128, 128, 255);
+        // color dodge only brightens the backdrop, so painting with black changes nothing
+        assert_eq!(Color::BLACK.blend_over(gray, BlendMode::ColorDodge), gray);
+        // color burn only darkens the backdrop, so painting with white changes nothing
+        assert_eq!(Color::WHITE.blend_over(gray, BlendMode::ColorBurn), gray);
+        // difference with black leaves the backdrop, and the source when swapped
+        let red = Color::new(200, 50, 50, 255);
+        assert_eq!(Color::BLACK.blend_over(red, BlendMode::Difference), red);
+    }
+
+    #[test]
+    fn overlay_keys_off_the_backdrop() {
+        let src = Color::new(200, 50, 50, 255);
+        // overlay keeps the backdrop's shadows and highlights
+        assert_eq!(
+            src.blend_over(Color::BLACK, BlendMode::Overlay),
+            Color::BLACK
+        );
+        assert_eq!(
+            src.blend_over(Color::WHITE, BlendMode::Overlay),
+            Color::WHITE
+        );
+        // hard light keys off the source instead
+        assert_eq!(
+            Color::BLACK.blend_over(Color::WHITE, BlendMode::HardLight),
+            Color::BLACK
+        );
+        assert_eq!(
+            Color::BLACK.blend_over(Color::WHITE, BlendMode::Overlay),
+            Color::WHITE
+        );
+    }
+
+    #[test]
+    fn overlay_is_hard_light_with_layers_swapped() {
+        for s in SAMPLES {
+            for b in SAMPLES {
+                assert_eq!(
+                    s.blend_over(b, BlendMode::Overlay),
+                    b.blend_over(s, BlendMode::HardLight),
+                    "{s:?} over {b:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn composite_source_over_matches_blend_over() {
+        for s in SAMPLES {
+            for b in SAMPLES {
+                let s = s.with_alpha(160);
+                for mode in [BlendMode::Normal, BlendMode::Multiply, BlendMode::Overlay] {
+                    assert_eq!(
+                        s.composite(b, CompositeOp::SourceOver, mode),
+                        s.blend_over(b, mode)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn composite_source_in_takes_backdrop_alpha() {
+        let red = Color::new(255, 0, 0, 255);
+        let half_blue = Color::new(0, 0, 255, 128);
+        // source-in keeps the source colour, masked by the backdrop's coverage
+        let out = red.composite(half_blue, CompositeOp::SourceIn, BlendMode::Normal);
+        assert_eq!(out, Color::new(255, 0, 0, 128));
+        // a transparent backdrop masks everything away
+        let out = red.composite(Color::TRANSPARENT, CompositeOp::SourceIn, BlendMode::Normal);
+        assert_eq!(out, Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn composite_clears_opaque_pairs() {
+        let red = Color::new(255, 0, 0, 255);
+        let blue = Color::new(0, 0, 255, 255);
+        for op in [
+            CompositeOp::Clear,
+            CompositeOp::Xor,
+            CompositeOp::DestinationOut,
+            CompositeOp::SourceOut,
+        ] {
+            assert_eq!(
+                red.composite(blue, op, BlendMode::Normal),
+                Color::TRANSPARENT,
+                "{op:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn composite_destination_ops_keep_backdrop() {
+        let red = Color::new(255, 0, 0, 255);
+        let blue = Color::new(0, 0, 255, 255);
+        for op in [
+            CompositeOp::DestinationOver,
+            CompositeOp::DestinationIn,
+            CompositeOp::DestinationAtop,
+        ] {
+            assert_eq!(red.composite(blue, op, BlendMode::Normal), blue, "{op:?}");
+        }
+        for op in [CompositeOp::Copy, CompositeOp::SourceAtop] {
+            assert_eq!(red.composite(blue, op, BlendMode::Normal), red, "{op:?}");
+        }
+    }
+
+    #[test]
+    fn from_hsla_takes_the_same_ranges_as_from_hsl() {
+        assert_eq!(
+            Color::from_hsla([120.0, 100.0, 25.0, 1.0]),
+            Color::new(0, 128, 0, 255)
+        );
+        for c in SAMPLES {
+            let [h, s, l, _] = c.into_hsla();
+            assert_eq!(Color::from_hsla([h, s, l, 1.0]), Color::from_hsl([h, s, l]));
+            assert_eq!(Color::from_hsla(c.into_hsla()), c);
+        }
+        let translucent = Color::new(250, 250, 210, 128);
+        assert_eq!(Color::from_hsla(translucent.into_hsla()), translucent);
+    }
+
+    fn assert_close<const N: usize>(got: [ColorFloat; N], want: [ColorFloat; N], tol: ColorFloat) {
+        for (g, w) in got.iter().zip(want) {
+            assert!((g - w).abs() <= tol, "got {got:?}, want {want:?}");
+        }
+    }
+
+    /// Every 15th value per channel, which includes 0 and 255.
+    fn grid() -> impl Iterator<Item = Color> {
+        (0..=255u8).step_by(15).flat_map(|r| {
+            (0..=255u8).step_by(15).flat_map(move |g| {
+                (0..=255u8)
+                    .step_by(15)
+                    .map(move |b| Color::new(r, g, b, 255))
+            })
+        })
+    }
+
+    #[test]
+    fn hsv_hwb_cmyk_reference_values() {
+        let orange = Color::new(255, 165, 0, 255);
+        assert_close(orange.into_hsv(), [38.824, 100.0, 100.0], 0.01);
+        assert_close(orange.into_hwb(), [38.824, 0.0, 0.0], 0.01);
+        assert_eq!(Color::from_hsv([38.824, 100.0, 100.0]), orange);
+
+        let medium_sea_green = Color::new(60, 179, 113, 255);
+        assert_close(medium_sea_green.into_hsv(), [146.723, 66.480, 70.196], 0.01);
+        assert_close(medium_sea_green.into_hwb(), [146.723, 23.529, 29.804], 0.01);
+        assert_eq!(Color::from_hwb([146.72, 23.53, 29.8]), medium_sea_green);
+
+        let indigo = Color::new(75, 0, 130, 255);
+        assert_close(indigo.into_cmyk(), [42.308, 100.0, 0.0, 49.020], 0.01);
+        assert_eq!(Color::from_cmyk([42.308, 100.0, 0.0, 49.020]), indigo);
+        assert_close(Color::BLACK.into_cmyk(), [0.0, 0.0, 0.0, 100.0], 0.0);
+        assert_close(Color::WHITE.into_cmyk(), [0.0; 4], 0.0);
+    }
+
+    #[test]
+    fn lab_reference_values() {
+        // CSS Color 4 sample values for sRGB red
+        let red = Color::new(255, 0, 0, 255);
+        assert_close(red.into_lab(), [54.291, 80.805, 69.891], 0.05);
+        assert_close(red.into_lch(), [54.291, 106.837, 40.853], 0.05);
+        assert_close(red.into_lab_d65(), [53.241, 80.092, 67.203], 0.05);
+        assert_close(red.into_lch_d65(), [53.241, 104.552, 39.999], 0.05);
+        assert_eq!(Color::from_lab([54.291, 80.805, 69.891]), red);
+        assert_eq!(Color::from_lch_d65([53.241, 104.552, 39.999]), red);
+    }
+
+    #[test]
+    fn lab_white_and_black_are_fixed_for_both_white_points() {
+        for (lab, lch) in [
+            (Color::WHITE.into_lab(), Color::WHITE.into_lch()),
+            (Color::WHITE.into_lab_d65(), Color::WHITE.into_lch_d65()),
+        ] {
+            assert_close(lab, [100.0, 0.0, 0.0], 0.01);
+            assert_close([lch[0], lch[1]], [100.0, 0.0], 0.01);
+        }
+        for lab in [Color::BLACK.into_lab(), Color::BLACK.into_lab_d65()] {
+            assert_close(lab, [0.0; 3], 0.01);
+        }
+        assert_eq!(Color::from_lab([100.0, 0.0, 0.0]), Color::WHITE);
+        assert_eq!(Color::from_lab_d65([100.0, 0.0, 0.0]), Color::WHITE);
+        assert_eq!(Color::from_lch([100.0, 0.0, 123.0]), Color::WHITE);
+        assert_eq!(Color::from_lch_d65([100.0, 0.0, 123.0]), Color::WHITE);
+        assert_eq!(Color::from_lab([0.0; 3]), Color::BLACK);
+        assert_eq!(Color::from_lab_d65([0.0; 3]), Color::BLACK);
+        assert_eq!(Color::from_lch([0.0; 3]), Color::BLACK);
+        assert_eq!(Color::from_lch_d65([0.0; 3]), Color::BLACK);
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        for c in grid() {
+            assert_eq!(Color::from_hsv(c.into_hsv()), c, "hsv");
+            assert_eq!(Color::from_hwb(c.into_hwb()), c, "hwb");
+            assert_eq!(Color::from_cmyk(c.into_cmyk()), c, "cmyk");
+            assert_eq!(Color::from_lab(c.into_lab()), c, "lab");
+            assert_eq!(Color::from_lch(c.into_lch()), c, "lch");
+            assert_eq!(Color::from_lab_d65(c.into_lab_d65()), c, "lab d65");
+            assert_eq!(Color::from_lch_d65(c.into_lch_d65()), c, "lch d65");
+        }
+    }
+
+    #[test]
+    fn alpha_variants_round_trip_and_keep_alpha() {
+        for (i, c) in grid().enumerate() {
+            let [r, g, b, _] = c.into_rgba();
+            let c = Color::new(r, g, b, (i * 7) as u8);
+            assert_eq!(Color::from_hsva(c.into_hsva()), c, "hsva");
+            assert_eq!(Color::from_hwba(c.into_hwba()), c, "hwba");
+            assert_eq!(Color::from_cmyka(c.into_cmyka()), c, "cmyka");
+            assert_eq!(Color::from_laba(c.into_laba()), c, "laba");
+            assert_eq!(Color::from_lcha(c.into_lcha()), c, "lcha");
+            assert_eq!(Color::from_laba_d65(c.into_laba_d65()), c, "laba d65");
+            assert_eq!(Color::from_lcha_d65(c.into_lcha_d65()), c, "lcha d65");
+        }
+        let translucent = Color::new(75, 0, 130, 128);
+        assert_eq!(translucent.into_hsva()[3], 128.0 / 255.0);
+        assert_eq!(translucent.into_cmyka()[4], 128.0 / 255.0);
+        assert_eq!(translucent.into_lcha_d65()[3], 128.0 / 255.0);
+    }
+}
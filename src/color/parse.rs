@@ -2,7 +2,9 @@
 
 use core::fmt;
 
-use crate::color::model::Color;
+#[cfg(not(feature = "std"))]
+use crate::color::math::FloatMath;
+use crate::color::{ColorFloat, model::Color, named::named_color};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ColorParseError {
@@ -11,6 +13,9 @@ pub enum ColorParseError {
     InvalidHex,
     InvalidFunc,
     OutOfRange,
+    UnknownName,
+    /// `currentcolor` was used without a current color to resolve it against.
+    CurrentColor,
 }
 
 impl fmt::Display for ColorParseError {
@@ -20,14 +25,16 @@ impl fmt::Display for ColorParseError {
             Empty => "empty color string",
             InvalidLength => "invalid hex length",
             InvalidHex => "invalid hex digits",
-            InvalidFunc => "invalid rgb()/rgba() function",
+            InvalidFunc => "invalid color function",
             OutOfRange => "component out of range",
+            UnknownName => "unknown color name",
+            CurrentColor => "currentcolor needs a current color to resolve against",
         };
         f.write_str(msg)
     }
 }
 #[cfg(feature = "std")]
-impl std::error::Error for ColorParseError {}
+impl core::error::Error for ColorParseError {}
 
 /// Parse a hex color from a string.
 ///
@@ -105,85 +112,361 @@ fn parse_hex(hex: &str) -> Result<Color, ColorParseError> {
     Ok(Color::from_rgba([r, g, b, a]))
 }
 
-/// Parse a CSS rgb function.
+/// A single component of a CSS color function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Component {
+    Number(ColorFloat),
+    Percentage(ColorFloat),
+    /// An angle with a unit (deg, rad, turn, grad), stored in degrees.
+    Angle(ColorFloat),
+    /// The `none` keyword (missing component), which resolves to zero.
+    None,
+}
+
+/// The components of a CSS color function.
+///
+/// Both the legacy comma-separated syntax (`rgb(1, 2, 3, 0.5)`) and the
+/// modern space-separated syntax (`rgb(1 2 3 / 50%)`) are accepted.
+/// Nothing is allocated; at most three color components plus an alpha are kept.
+#[derive(Clone, Copy, Debug)]
+struct FuncArgs {
+    channels: [Component; 3],
+    alpha: Option<Component>,
+    /// Whether the arguments used the comma-separated legacy syntax.
+    legacy: bool,
+}
+
+/// Parse a CSS `<number>`, allowing signs, decimals, and exponents
+/// (`+255`, `.5`, `1e2`), but not `inf`/`nan` spellings.
+fn parse_number(tok: &str) -> Result<ColorFloat, ColorParseError> {
+    use ColorParseError::*;
+
+    let valid = !tok.is_empty()
+        && tok.bytes().any(|c| c.is_ascii_digit())
+        && tok
+            .bytes()
+            .all(|c| c.is_ascii_digit() || matches!(c, b'.' | b'e' | b'E' | b'+' | b'-'));
+    if !valid {
+        return Err(InvalidFunc);
+    }
+
+    let n = tok.parse::<ColorFloat>().map_err(|_| InvalidFunc)?;
+    if !n.is_finite() {
+        return Err(OutOfRange);
+    }
+    Ok(n)
+}
+
+/// Strip a case-insensitive ASCII suffix from a token.
+fn strip_suffix_ignore_case<'a>(tok: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = tok.len().checked_sub(suffix.len())?;
+    if !tok.is_char_boundary(split) {
+        return None;
+    }
+    let (head, tail) = tok.split_at(split);
+    tail.eq_ignore_ascii_case(suffix).then_some(head)
+}
+
+/// Parse one component token: a number, a percentage, an angle, or `none`.
+fn parse_component(tok: &str) -> Result<Component, ColorParseError> {
+    if tok.eq_ignore_ascii_case("none") {
+        return Ok(Component::None);
+    }
+    if let Some(p) = tok.strip_suffix('%') {
+        return Ok(Component::Percentage(parse_number(p)?));
+    }
+
+    // "grad" has to be checked before "rad"
+    const ANGLE_UNITS: [(&str, ColorFloat); 4] = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / core::f64::consts::PI as ColorFloat),
+        ("turn", 360.0),
+    ];
+    for (unit, to_deg) in ANGLE_UNITS {
+        if let Some(n) = strip_suffix_ignore_case(tok, unit) {
+            return Ok(Component::Angle(parse_number(n)? * to_deg));
+        }
+    }
+
+    Ok(Component::Number(parse_number(tok)?))
+}
+
+/// Split the inside of a CSS color function into its components.
+fn parse_func_args(args: &str) -> Result<FuncArgs, ColorParseError> {
+    use ColorParseError::*;
+
+    let mut channels = [Component::None; 3];
+    let mut alpha = None;
+
+    if args.contains(',') {
+        // legacy syntax: r, g, b[, a]. no `/` or `none` allowed here
+        let mut n = 0;
+        for tok in args.split(',').map(|t| t.trim()) {
+            if tok.is_empty() || tok.contains(char::is_whitespace) || tok.contains('/') {
+                return Err(InvalidFunc);
+            }
+            let c = parse_component(tok)?;
+            if c == Component::None {
+                return Err(InvalidFunc);
+            }
+            match n {
+                0..3 => channels[n] = c,
+                3 => alpha = Some(c),
+                _ => return Err(InvalidFunc),
+            }
+            n += 1;
+        }
+        if n < 3 {
+            return Err(InvalidFunc);
+        }
+
+        return Ok(FuncArgs {
+            channels,
+            alpha,
+            legacy: true,
+        });
+    }
+
+    // modern syntax: r g b [/ a]
+    let (color, alpha_str) = match args.split_once('/') {
+        Some((c, a)) => (c, Some(a.trim())),
+        None => (args, None),
+    };
+
+    let mut n = 0;
+    for tok in color.split_ascii_whitespace() {
+        if n >= 3 {
+            return Err(InvalidFunc);
+        }
+        channels[n] = parse_component(tok)?;
+        n += 1;
+    }
+    if n != 3 {
+        return Err(InvalidFunc);
+    }
+
+    if let Some(a) = alpha_str {
+        if a.is_empty() || a.contains(char::is_whitespace) || a.contains('/') {
+            return Err(InvalidFunc);
+        }
+        alpha = Some(parse_component(a)?);
+    }
+
+    Ok(FuncArgs {
+        channels,
+        alpha,
+        legacy: false,
+    })
+}
+
+/// Resolve a number/percentage component, where 100% maps to `percent_ref`.
+/// `none` resolves to zero; angles aren't allowed.
+fn resolve(c: Component, percent_ref: ColorFloat) -> Result<ColorFloat, ColorParseError> {
+    match c {
+        Component::Number(n) => Ok(n),
+        Component::Percentage(p) => Ok(p / 100.0 * percent_ref),
+        Component::None => Ok(0.0),
+        Component::Angle(_) => Err(ColorParseError::InvalidFunc),
+    }
+}
+
+/// Resolve a hue component to degrees. Bare numbers are degrees.
+fn resolve_hue(c: Component) -> Result<ColorFloat, ColorParseError> {
+    match c {
+        Component::Number(deg) | Component::Angle(deg) => Ok(deg),
+        Component::None => Ok(0.0),
+        Component::Percentage(_) => Err(ColorParseError::InvalidFunc),
+    }
+}
+
+/// Resolve an alpha component to 0..=255. A missing alpha is fully opaque.
+fn resolve_alpha(alpha: Option<Component>) -> Result<u8, ColorParseError> {
+    let a = match alpha {
+        Some(c) => resolve(c, 1.0)?,
+        None => 1.0,
+    };
+    Ok((a.clamp(0.0, 1.0) * 255.0 + 0.5).floor() as u8)
+}
+
+/// Parse the arguments of a function that only has the modern syntax.
+fn parse_modern_args(args: &str) -> Result<FuncArgs, ColorParseError> {
+    let parsed = parse_func_args(args)?;
+    if parsed.legacy {
+        return Err(ColorParseError::InvalidFunc);
+    }
+    Ok(parsed)
+}
+
+/// Parse a CSS rgb()/rgba() function (`rgba` is an alias of `rgb`).
 ///
 /// The allowed styles are:
-/// rgb(r,g,b)
-/// rgb(r g b) (TODO: needs implementation)
-/// rgb(r% g% b%) (TODO: needs implementation)
-/// rgb(r g b / a) (TODO: needs implementation)
+/// * rgb(r, g, b) and rgb(r, g, b, a)
+/// * rgb(r%, g%, b%) and rgb(r%, g%, b%, a)
+/// * rgb(r g b) and rgb(r g b / a), where any component may be a number,
+///   a percentage, or `none`
+///
+/// Numbers may be signed or fractional. Channels outside 0..=255 (or 0%..=100%)
+/// and alphas outside 0..=1 are clamped, as CSS specifies.
 fn parse_css_rgb(args: &str) -> Result<Color, ColorParseError> {
     use ColorParseError::*;
 
-    let nums: Vec<&str> = args.split(',').map(|t| t.trim()).collect();
-    if nums.len() != 3 {
-        return Err(InvalidFunc);
+    let FuncArgs {
+        channels,
+        alpha,
+        legacy,
+    } = parse_func_args(args)?;
+
+    // the legacy syntax can't mix numbers and percentages
+    if legacy {
+        let percents = channels
+            .iter()
+            .filter(|c| matches!(c, Component::Percentage(_)))
+            .count();
+        if percents != 0 && percents != 3 {
+            return Err(InvalidFunc);
+        }
     }
 
-    let r = nums[0]
-        .parse::<u16>()
-        .ok()
-        .filter(|&v| v <= 255)
-        .ok_or(OutOfRange)? as u8;
-    let g = nums[1]
-        .parse::<u16>()
-        .ok()
-        .filter(|&v| v <= 255)
-        .ok_or(OutOfRange)? as u8;
-    let b = nums[2]
-        .parse::<u16>()
-        .ok()
-        .filter(|&v| v <= 255)
-        .ok_or(OutOfRange)? as u8;
+    let channel = |c: Component| -> Result<u8, ColorParseError> {
+        Ok((resolve(c, 255.0)?.clamp(0.0, 255.0) + 0.5).floor() as u8)
+    };
 
-    Ok(Color::from_rgb([r, g, b]))
+    Ok(Color::from_rgba([
+        channel(channels[0])?,
+        channel(channels[1])?,
+        channel(channels[2])?,
+        resolve_alpha(alpha)?,
+    ]))
 }
 
-/// Parse a CSS rgba function.
+/// Parse a CSS hsl()/hsla() function (`hsla` is an alias of `hsl`).
 ///
 /// The allowed styles are:
-/// rgba(r,g,b,a)
-/// rgba(r g b a) (TODO: needs implementation)
-/// rgba(r% g% b% a%) (TODO: needs implementation)
-/// rgba(r g b / a) (TODO: needs implementation)
-fn parse_css_rgba(args: &str) -> Result<Color, ColorParseError> {
+/// * hsl(h, s%, l%) and hsl(h, s%, l%, a)
+/// * hsl(h s l) and hsl(h s l / a), where saturation and lightness may be
+///   numbers or percentages, and any component may be `none`
+///
+/// The hue is a number of degrees or an angle (deg, rad, turn, grad).
+fn parse_css_hsl(args: &str) -> Result<Color, ColorParseError> {
     use ColorParseError::*;
 
-    let nums: Vec<&str> = args.split(',').map(|t| t.trim()).collect();
-    if nums.len() != 4 {
+    let FuncArgs {
+        channels: [h, s, l],
+        alpha,
+        legacy,
+    } = parse_func_args(args)?;
+
+    // the legacy syntax needs percentages for saturation and lightness
+    if legacy && !(matches!(s, Component::Percentage(_)) && matches!(l, Component::Percentage(_))) {
         return Err(InvalidFunc);
     }
 
-    let r = nums[0]
-        .parse::<u16>()
-        .ok()
-        .filter(|&v| v <= 255)
-        .ok_or(OutOfRange)? as u8;
-    let g = nums[1]
-        .parse::<u16>()
-        .ok()
-        .filter(|&v| v <= 255)
-        .ok_or(OutOfRange)? as u8;
-    let b = nums[2]
-        .parse::<u16>()
-        .ok()
-        .filter(|&v| v <= 255)
-        .ok_or(OutOfRange)? as u8;
-
-    // allow 0.0..1.0 or 0..255
-    let a = if let Ok(f) = nums[3].parse::<f32>() {
-        (f.clamp(0.0, 1.0) * 255.0 + 0.5).floor() as u8
-    } else {
-        nums[3]
-            .parse::<u16>()
-            .ok()
-            .filter(|&v| v <= 255)
-            .ok_or(OutOfRange)? as u8
-    };
+    let h = resolve_hue(h)?;
+    let s = resolve(s, 100.0)?.clamp(0.0, 100.0);
+    let l = resolve(l, 100.0)?.clamp(0.0, 100.0);
 
-    Ok(Color::from_rgba([r, g, b, a]))
+    Ok(Color::from_hsl([h, s, l]).with_alpha(resolve_alpha(alpha)?))
+}
+
+/// Parse a CSS hwb() function.
+///
+/// The allowed styles are:
+/// * hwb(h w b) and hwb(h w b / a), where whiteness and blackness may be
+///   numbers or percentages, and any component may be `none`
+fn parse_css_hwb(args: &str) -> Result<Color, ColorParseError> {
+    let FuncArgs {
+        channels: [h, w, b],
+        alpha,
+        ..
+    } = parse_modern_args(args)?;
+
+    let h = resolve_hue(h)?;
+    let w = resolve(w, 100.0)?;
+    let b = resolve(b, 100.0)?;
+
+    Ok(Color::from_hwb([h, w, b]).with_alpha(resolve_alpha(alpha)?))
 }
 
+/// Parse a CSS lab() function.
+///
+/// The allowed styles are:
+/// * lab(L a b) and lab(L a b / alpha), where 100% is 100 for L and 125 for a and b
+fn parse_css_lab(args: &str) -> Result<Color, ColorParseError> {
+    let FuncArgs {
+        channels: [l, a, b],
+        alpha,
+        ..
+    } = parse_modern_args(args)?;
+
+    let l = resolve(l, 100.0)?.clamp(0.0, 100.0);
+    let a = resolve(a, 125.0)?;
+    let b = resolve(b, 125.0)?;
+
+    Ok(Color::from_lab([l, a, b]).with_alpha(resolve_alpha(alpha)?))
+}
+
+/// Parse a CSS lch() function.
+///
+/// The allowed styles are:
+/// * lch(L C h) and lch(L C h / alpha), where 100% is 100 for L and 150 for C
+fn parse_css_lch(args: &str) -> Result<Color, ColorParseError> {
+    let FuncArgs {
+        channels: [l, c, h],
+        alpha,
+        ..
+    } = parse_modern_args(args)?;
+
+    let l = resolve(l, 100.0)?.clamp(0.0, 100.0);
+    let c = resolve(c, 150.0)?.max(0.0);
+    let h = resolve_hue(h)?;
+
+    Ok(Color::from_lch([l, c, h]).with_alpha(resolve_alpha(alpha)?))
+}
+
+/// Parse a CSS oklab() function.
+///
+/// The allowed styles are:
+/// * oklab(L a b) and oklab(L a b / alpha), where 100% is 1.0 for L and 0.4 for a and b
+fn parse_css_oklab(args: &str) -> Result<Color, ColorParseError> {
+    let FuncArgs {
+        channels: [l, a, b],
+        alpha,
+        ..
+    } = parse_modern_args(args)?;
+
+    let l = resolve(l, 1.0)?.clamp(0.0, 1.0);
+    let a = resolve(a, 0.4)?;
+    let b = resolve(b, 0.4)?;
+
+    Ok(Color::from_oklab([l, a, b]).with_alpha(resolve_alpha(alpha)?))
+}
+
+/// Parse a CSS oklch() function.
+///
+/// The allowed styles are:
+/// * oklch(L C h) and oklch(L C h / alpha), where 100% is 1.0 for L and 0.4 for C
+///
+/// Out of gamut colors are brought into sRGB by reducing chroma.
+fn parse_css_oklch(args: &str) -> Result<Color, ColorParseError> {
+    let FuncArgs {
+        channels: [l, c, h],
+        alpha,
+        ..
+    } = parse_modern_args(args)?;
+
+    let l = resolve(l, 1.0)?.clamp(0.0, 1.0);
+    let c = resolve(c, 0.4)?.max(0.0);
+    let h = resolve_hue(h)?;
+
+    Ok(Color::from_oklch([l, c, h]).with_alpha(resolve_alpha(alpha)?))
+}
+
+/// Parse a color string.
+///
+/// Hex (`#RGB`, `#RGBA`, `#RRGGBB`, `#RRGGBBAA`), CSS color functions, CSS named
+/// colors, and `transparent` are accepted. `currentcolor` is rejected with
+/// `ColorParseError::CurrentColor`; use `parse_color_with_current` to resolve it.
 pub fn parse_color(mut s: &str) -> Result<Color, ColorParseError> {
     use ColorParseError::*;
 
@@ -198,30 +481,47 @@ pub fn parse_color(mut s: &str) -> Result<Color, ColorParseError> {
         return parse_hex(hex);
     }
 
-    // CSS-like: rgb(r,g,b) / rgba(r,g,b,a[0..1])
-    // TODO: Parsing support is “CSS2-ish” right now
-    // We only handle rgb(r,g,b) and rgba(r,g,b,a) with integers and commas. Modern CSS allows:
-    // Space-separated: rgb(255 0 0)
-    // Percentages: rgb(100% 0% 0%)
-    // Slash alpha: rgb(255 0 0 / 0.5)
-    // HSL: hsl(210 50% 40% / 0.7)
-    // Plan: add a small tokenizer that accepts commas or spaces, and an optional / alpha token.
-    // We already have HSL converters, so once the parser extracts (h, s%, l%, a?), we can call from_hsl.
-    // Also allow for things like rgb(+255, +255, +255) (CSS allowed)
-    let lower = s.to_ascii_lowercase();
-    if let Some(args) = lower.strip_prefix("rgb(").and_then(|x| x.strip_suffix(')')) {
-        return parse_css_rgb(args);
+    // CSS-like: name(args)
+    if let Some((name, rest)) = s.split_once('(') {
+        let args = rest.strip_suffix(')').ok_or(InvalidFunc)?;
+        let parse: fn(&str) -> Result<Color, ColorParseError> = match name {
+            _ if name.eq_ignore_ascii_case("rgb") || name.eq_ignore_ascii_case("rgba") => {
+                parse_css_rgb
+            }
+            _ if name.eq_ignore_ascii_case("hsl") || name.eq_ignore_ascii_case("hsla") => {
+                parse_css_hsl
+            }
+            _ if name.eq_ignore_ascii_case("hwb") => parse_css_hwb,
+            _ if name.eq_ignore_ascii_case("lab") => parse_css_lab,
+            _ if name.eq_ignore_ascii_case("lch") => parse_css_lch,
+            _ if name.eq_ignore_ascii_case("oklab") => parse_css_oklab,
+            _ if name.eq_ignore_ascii_case("oklch") => parse_css_oklch,
+            _ => return Err(InvalidFunc),
+        };
+        return parse(args);
     }
-    if let Some(args) = lower
-        .strip_prefix("rgba(")
-        .and_then(|x| x.strip_suffix(')'))
-    {
-        return parse_css_rgba(args);
+
+    // Named colors
+    if s.eq_ignore_ascii_case("currentcolor") {
+        return Err(CurrentColor);
+    }
+    if s.bytes().all(|c| c.is_ascii_alphabetic()) {
+        return named_color(s).ok_or(UnknownName);
     }
 
     Err(InvalidFunc)
 }
 
+/// Parse a color string, resolving `currentcolor` to `current`.
+///
+/// Everything else behaves exactly like `parse_color`.
+pub fn parse_color_with_current(s: &str, current: Color) -> Result<Color, ColorParseError> {
+    if s.trim().eq_ignore_ascii_case("currentcolor") {
+        return Ok(current);
+    }
+    parse_color(s)
+}
+
 impl core::str::FromStr for Color {
     type Err = ColorParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -234,3 +534,212 @@ impl TryFrom<&str> for Color {
         parse_color(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba(s: &str) -> [u8; 4] {
+        parse_color(s).unwrap().into_rgba()
+    }
+
+    #[test]
+    fn rgb_legacy_and_modern_syntax_agree() {
+        let expected = [255, 128, 0, 255];
+        assert_eq!(rgba("rgb(255, 128, 0)"), expected);
+        assert_eq!(rgba("rgb(255,128,0)"), expected);
+        assert_eq!(rgba("rgba(255, 128, 0)"), expected);
+        assert_eq!(rgba("rgb(255 128 0)"), expected);
+        assert_eq!(rgba("RGB(  255   128 0 )"), expected);
+        assert_eq!(rgba("rgba(255 128 0)"), expected);
+    }
+
+    #[test]
+    fn rgb_alpha() {
+        assert_eq!(rgba("rgb(1, 2, 3, 0.5)"), [1, 2, 3, 128]);
+        assert_eq!(rgba("rgba(1, 2, 3, 50%)"), [1, 2, 3, 128]);
+        assert_eq!(rgba("rgb(1 2 3 / 0.5)"), [1, 2, 3, 128]);
+        assert_eq!(rgba("rgb(1 2 3 / 25%)"), [1, 2, 3, 64]);
+        assert_eq!(rgba("rgb(1 2 3/0)"), [1, 2, 3, 0]);
+        assert_eq!(rgba("rgb(1 2 3 / .2e1)"), [1, 2, 3, 255]);
+    }
+
+    #[test]
+    fn rgb_percentages() {
+        assert_eq!(rgba("rgb(100%, 50%, 0%)"), [255, 128, 0, 255]);
+        assert_eq!(rgba("rgb(100% 50% 0% / 100%)"), [255, 128, 0, 255]);
+        // the modern syntax may mix numbers and percentages, the legacy one may not
+        assert_eq!(rgba("rgb(100% 128 0)"), [255, 128, 0, 255]);
+        assert_eq!(
+            parse_color("rgb(100%, 128, 0)"),
+            Err(ColorParseError::InvalidFunc)
+        );
+    }
+
+    #[test]
+    fn rgb_none() {
+        assert_eq!(rgba("rgb(none 128 none)"), [0, 128, 0, 255]);
+        assert_eq!(rgba("rgb(10 20 30 / none)"), [10, 20, 30, 0]);
+        // `none` is modern syntax only
+        assert_eq!(
+            parse_color("rgb(none, 128, 0)"),
+            Err(ColorParseError::InvalidFunc)
+        );
+    }
+
+    #[test]
+    fn rgb_clamps_out_of_range_values() {
+        assert_eq!(rgba("rgb(300, -20, 127.6)"), [255, 0, 128, 255]);
+        assert_eq!(rgba("rgb(+1e3 -0 150%)"), [255, 0, 255, 255]);
+        assert_eq!(rgba("rgb(0 0 0 / 2)"), [0, 0, 0, 255]);
+        assert_eq!(rgba("rgb(0 0 0 / -50%)"), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn rgb_rejects_malformed_arguments() {
+        for s in [
+            // mixed comma and space separators
+            "rgb(1, 2 3)",
+            "rgb(1 2, 3)",
+            "rgb(1, 2, 3 / 0.5)",
+            "rgb(1 2 3, 0.5)",
+            // wrong component counts
+            "rgb(1, 2)",
+            "rgb(1 2)",
+            "rgb(1, 2, 3, 4, 5)",
+            "rgb(1 2 3 4)",
+            "rgb(1, , 3)",
+            "rgb(1 2 3 /)",
+            "rgb(1 2 3 / 0.5 / 0.5)",
+            // not numbers
+            "rgb(1 2 three)",
+            "rgb(inf 0 0)",
+            "rgb(nan 0 0)",
+            "rgb(1deg 2 3)",
+            "rgb(1 2 3",
+        ] {
+            assert!(parse_color(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn parse_number_accepts_css_numbers() {
+        assert_eq!(parse_number("+255"), Ok(255.0));
+        assert_eq!(parse_number(".5"), Ok(0.5));
+        assert_eq!(parse_number("1e2"), Ok(100.0));
+        assert_eq!(parse_number("-0.25"), Ok(-0.25));
+        for tok in ["", "e", ".", "inf", "NaN", "1x", "0x10"] {
+            assert_eq!(
+                parse_number(tok),
+                Err(ColorParseError::InvalidFunc),
+                "{tok}"
+            );
+        }
+        assert_eq!(parse_number("1e999"), Err(ColorParseError::OutOfRange));
+    }
+
+    fn assert_close(s: &str, expected: [u8; 4]) {
+        let got = rgba(s);
+        for (x, y) in got.into_iter().zip(expected) {
+            assert!(x.abs_diff(y) <= 1, "{s}: {got:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn hsl_known_values() {
+        assert_eq!(rgba("hsl(120 100% 25%)"), [0, 128, 0, 255]);
+        assert_eq!(rgba("hsl(120, 100%, 25%)"), [0, 128, 0, 255]);
+        assert_eq!(rgba("hsla(0, 100%, 50%, 0.5)"), [255, 0, 0, 128]);
+        assert_eq!(rgba("hsl(240 100 50 / 20%)"), [0, 0, 255, 51]);
+        assert_eq!(rgba("hsl(none 0% 100%)"), [255, 255, 255, 255]);
+        // the legacy syntax needs percentages for saturation and lightness
+        assert_eq!(
+            parse_color("hsl(120, 100, 25)"),
+            Err(ColorParseError::InvalidFunc)
+        );
+    }
+
+    #[test]
+    fn hue_units_and_wrap() {
+        let cyan = [0, 255, 255, 255];
+        for s in [
+            "hsl(180 100% 50%)",
+            "hsl(180deg 100% 50%)",
+            "hsl(0.5turn 100% 50%)",
+            "hsl(200grad 100% 50%)",
+            "hsl(540 100% 50%)",
+            "hsl(-180 100% 50%)",
+        ] {
+            assert_eq!(rgba(s), cyan, "{s}");
+        }
+        assert_close("hsl(3.14159rad 100% 50%)", cyan);
+    }
+
+    #[test]
+    fn hwb_known_values() {
+        assert_eq!(rgba("hwb(0 0% 0%)"), [255, 0, 0, 255]);
+        assert_eq!(rgba("hwb(120 0% 50%)"), [0, 128, 0, 255]);
+        assert_eq!(rgba("hwb(240 20% 0% / 0.5)"), [51, 51, 255, 128]);
+        // whiteness and blackness adding up past 100% give a gray
+        assert_eq!(rgba("hwb(90 50% 50%)"), [128, 128, 128, 255]);
+        assert_eq!(rgba("hwb(90 100% 100%)"), [128, 128, 128, 255]);
+        // hwb() has no legacy syntax
+        assert_eq!(
+            parse_color("hwb(0, 0%, 0%)"),
+            Err(ColorParseError::InvalidFunc)
+        );
+
+        assert_eq!(
+            Color::from_hwb([240.0, 0.0, 0.0]),
+            Color::new(0, 0, 255, 255)
+        );
+        assert_eq!(Color::from_hwb([0.0, 100.0, 0.0]), Color::WHITE);
+        assert_eq!(Color::from_hwb([0.0, 0.0, 100.0]), Color::BLACK);
+    }
+
+    #[test]
+    fn lab_and_lch_known_values() {
+        let [r, g, b, a] = rgba("lab(50% 0 0)");
+        assert!(r == g && g == b && a == 255, "{r} {g} {b}");
+        assert!((118..=120).contains(&r), "{r}");
+        assert_eq!(rgba("lab(100 0 0)"), [255, 255, 255, 255]);
+        assert_eq!(rgba("lab(0% 0 0 / 0.5)"), [0, 0, 0, 128]);
+        // sRGB red in D50 CIELAB and LCh
+        assert_close("lab(54.29 80.81 69.89)", [255, 0, 0, 255]);
+        assert_close("lch(54.29 106.84 40.85)", [255, 0, 0, 255]);
+        // zero chroma ignores the hue
+        assert_eq!(rgba("lch(50 0 123)"), rgba("lab(50 0 0)"));
+        assert_eq!(rgba("lch(50% none none)"), rgba("lab(50 0 0)"));
+
+        assert_eq!(Color::from_lab([100.0, 0.0, 0.0]), Color::WHITE);
+        assert_eq!(Color::from_lab([0.0, 0.0, 0.0]), Color::BLACK);
+        let red = Color::new(255, 0, 0, 255);
+        assert_eq!(Color::from_lab(red.into_lab()), red);
+        assert_eq!(Color::from_lch(red.into_lch()), red);
+        assert_eq!(
+            Color::from_lch([75.0, 0.0, 300.0]),
+            Color::from_lab([75.0, 0.0, 0.0])
+        );
+    }
+
+    #[test]
+    fn oklab_and_oklch_known_values() {
+        assert_eq!(rgba("oklab(1 0 0)"), [255, 255, 255, 255]);
+        assert_eq!(rgba("oklab(0% 0 0)"), [0, 0, 0, 255]);
+        assert_close("oklab(62.8% 0.225 0.126)", [255, 0, 0, 255]);
+        assert_close("oklch(0.628 0.2577 29.23)", [255, 0, 0, 255]);
+        // hues wrap around the circle
+        let base = rgba("oklch(0.7 0.1 40)");
+        for s in [
+            "oklch(0.7 0.1 400)",
+            "oklch(0.7 0.1 -320)",
+            "oklch(70% 25% 400deg)",
+        ] {
+            assert_eq!(rgba(s), base, "{s}");
+        }
+        assert_eq!(
+            parse_color("oklch(0.7 0.1 40%)"),
+            Err(ColorParseError::InvalidFunc)
+        );
+    }
+}
@@ -0,0 +1,304 @@
+#![allow(dead_code)]
+
+// Premultiplied-alpha storage and the integer SrcOver kernel from the renderer roadmap.
+// compositing premultiplied colors is just `out = src + dst * (1 - src.a)` per channel,
+// so the hot loop needs no division and no float conversion.
+// every channel is kept <= alpha, which is what lets that sum never overflow a u8.
+
+use crate::color::model::Color;
+
+/// An 8-bit sRGB color with its color channels premultiplied by alpha.
+///
+/// Every color channel is at most the alpha value.
+///
+/// # Fields
+///
+/// - `r` (`u8`) - The premultiplied red value.
+/// - `g` (`u8`) - The premultiplied green value.
+/// - `b` (`u8`) - The premultiplied blue value.
+/// - `a` (`u8`) - The alpha value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PremulColor {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+}
+
+impl PremulColor {
+    pub const TRANSPARENT: Self = Self::new(0, 0, 0, 0);
+
+    /// Create a new color from already premultiplied u8 values.
+    ///
+    /// Channels larger than `a` are clamped to `a`.
+    ///
+    /// # Arguments
+    ///
+    /// - `r` (`u8`) - The premultiplied red value.
+    /// - `g` (`u8`) - The premultiplied green value.
+    /// - `b` (`u8`) - The premultiplied blue value.
+    /// - `a` (`u8`) - The alpha value.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - A new premultiplied color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::premul::PremulColor;
+    ///
+    /// // half transparent white
+    /// let veil = PremulColor::new(128, 128, 128, 128);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self {
+            r: min(r, a),
+            g: min(g, a),
+            b: min(b, a),
+            a,
+        }
+    }
+
+    /// Premultiply a straight-alpha color.
+    ///
+    /// Each channel is rounded to the nearest premultiplied value.
+    /// Opaque colors convert losslessly in both directions.
+    ///
+    /// # Arguments
+    ///
+    /// - `color` (`Color`) - The color to premultiply.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The premultiplied color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::{Color, premul::PremulColor};
+    ///
+    /// let p = PremulColor::from_color(Color::new(255, 0, 0, 128));
+    /// assert_eq!(p.into_rgba(), [128, 0, 0, 128]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_color(color: Color) -> Self {
+        let [r, g, b, a] = color.into_rgba();
+        match a {
+            0 => Self::TRANSPARENT,
+            255 => Self { r, g, b, a },
+            _ => Self {
+                r: div255(r as u16 * a as u16),
+                g: div255(g as u16 * a as u16),
+                b: div255(b as u16 * a as u16),
+                a,
+            },
+        }
+    }
+
+    /// Convert back to a straight-alpha color.
+    ///
+    /// Each channel is rounded to the nearest straight value, so converting the result
+    /// back with `PremulColor::from_color` gives `self` again. The other direction is
+    /// lossy: premultiplying throws away precision at low alpha.
+    /// Fully transparent colors always come back as `Color::TRANSPARENT`.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`PremulColor`) - The color to unpremultiply.
+    ///
+    /// # Returns
+    ///
+    /// - `Color` - The straight-alpha color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::{Color, premul::PremulColor};
+    ///
+    /// let orchid = Color::new(218, 112, 214, 255);
+    /// assert_eq!(PremulColor::from_color(orchid).into_color(), orchid);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn into_color(self) -> Color {
+        let a = self.a as u16;
+        match self.a {
+            0 => Color::TRANSPARENT,
+            255 => Color::new(self.r, self.g, self.b, 255),
+            // channels are <= a, so the rounded quotient is <= 255
+            _ => Color::new(
+                ((self.r as u16 * 255 + a / 2) / a) as u8,
+                ((self.g as u16 * 255 + a / 2) / a) as u8,
+                ((self.b as u16 * 255 + a / 2) / a) as u8,
+                self.a,
+            ),
+        }
+    }
+
+    /// The raw premultiplied channels.
+    #[must_use]
+    #[inline]
+    pub const fn into_rgba(self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    /// The alpha value.
+    #[must_use]
+    #[inline]
+    pub const fn alpha(self) -> u8 {
+        self.a
+    }
+
+    /// Porter-Duff source over, in integer premultiplied sRGB.
+    ///
+    /// Computes `out = src + dst * (1 - src.a)` with exactly rounded division by 255.
+    /// Fully transparent sources return `dst` and opaque sources return `self`
+    /// without touching the backdrop.
+    ///
+    /// This is the premultiplied equivalent of `Color::over_srgb_fast`. Over an opaque
+    /// backdrop the two agree to within 1 per channel; over translucent backdrops the
+    /// rounding of the premultiplied channels can drift further at low output alpha.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`PremulColor`) - The source color.
+    /// - `dst` (`PremulColor`) - The backdrop color.
+    ///
+    /// # Returns
+    ///
+    /// - `PremulColor` - The composited color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::{Color, premul::PremulColor};
+    ///
+    /// let src = Color::new(70, 130, 180, 200);
+    /// let dst = Color::new(250, 128, 114, 255);
+    ///
+    /// let fast = src.over_srgb_fast(dst);
+    /// let premul = PremulColor::from(src).over(dst.into()).into_color();
+    ///
+    /// for (x, y) in fast.into_rgba().into_iter().zip(premul.into_rgba()) {
+    ///     assert!(x.abs_diff(y) <= 1);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn over(self, dst: PremulColor) -> PremulColor {
+        match self.a {
+            0 => dst,
+            255 => self,
+            _ => {
+                let inv = 255 - self.a as u16;
+                // src.c <= src.a and dst.c * inv / 255 <= inv, so none of these overflow
+                PremulColor {
+                    r: self.r + div255(dst.r as u16 * inv),
+                    g: self.g + div255(dst.g as u16 * inv),
+                    b: self.b + div255(dst.b as u16 * inv),
+                    a: self.a + div255(dst.a as u16 * inv),
+                }
+            }
+        }
+    }
+}
+
+impl From<Color> for PremulColor {
+    #[inline]
+    fn from(color: Color) -> Self {
+        Self::from_color(color)
+    }
+}
+
+impl From<PremulColor> for Color {
+    #[inline]
+    fn from(color: PremulColor) -> Self {
+        color.into_color()
+    }
+}
+
+/// `x / 255` rounded to nearest, exact for every `x` in `0..=255 * 255`.
+///
+/// Source: Jim Blinn, "Three Wrongs Make a Right" (IEEE CG&A, 1995).
+#[inline]
+pub(crate) const fn div255(x: u16) -> u8 {
+    let x = x as u32 + 128;
+    ((x + (x >> 8)) >> 8) as u8
+}
+
+#[inline]
+const fn min(x: u8, y: u8) -> u8 {
+    if x < y { x } else { y }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANNELS: [u8; 8] = [0, 1, 64, 127, 128, 200, 254, 255];
+
+    #[test]
+    fn div255_is_exact() {
+        for x in 0..=255 * 255 {
+            assert_eq!(div255(x) as u32, (x as u32 * 2 + 255) / 510, "{x}");
+        }
+    }
+
+    #[test]
+    fn over_matches_over_srgb_fast_on_opaque_backdrops() {
+        let backdrops = [
+            Color::BLACK,
+            Color::WHITE,
+            Color::new(250, 128, 114, 255),
+            Color::new(0, 100, 0, 255),
+        ];
+        for a in 0..=255 {
+            for c in CHANNELS {
+                let src = Color::new(c, 255 - c, c / 2, a);
+                for dst in backdrops {
+                    let fast = src.over_srgb_fast(dst).into_rgba();
+                    let premul = PremulColor::from(src)
+                        .over(dst.into())
+                        .into_color()
+                        .into_rgba();
+                    for (x, y) in fast.into_iter().zip(premul) {
+                        assert!(
+                            x.abs_diff(y) <= 1,
+                            "{src:?} over {dst:?}: {fast:?} vs {premul:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn opaque_colors_round_trip() {
+        for r in CHANNELS {
+            for g in CHANNELS {
+                for b in CHANNELS {
+                    let color = Color::new(r, g, b, 255);
+                    let premul = PremulColor::from(color);
+                    assert_eq!(premul.into_rgba(), [r, g, b, 255]);
+                    assert_eq!(Color::from(premul), color);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn over_keeps_channels_below_alpha() {
+        for a in 0..=255 {
+            for c in CHANNELS {
+                let src = PremulColor::new(c, c, c, a);
+                let out = src.over(PremulColor::new(200, 100, 255, 230));
+                let [r, g, b, a] = out.into_rgba();
+                assert!(r <= a && g <= a && b <= a, "{out:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,310 @@
+// serde support for Color and BlendMode.
+//
+// Color serializes as a hex string ("#RRGGBB", or "#RRGGBBAA" when translucent)
+// for human-readable formats and as an [r, g, b, a] tuple for binary ones.
+// it deserializes from anything `parse_color` accepts, from [r, g, b(, a)] arrays,
+// and from {r, g, b(, a)} maps, so theme files can use whichever is nicest.
+//
+// BlendMode and CompositeOp (de)serialize as their kebab-case names ("color-dodge",
+// "destination-out").
+
+use core::fmt;
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeTuple,
+};
+
+use crate::color::{
+    model::{BlendMode, Color, CompositeOp},
+    parse::parse_color,
+};
+
+/// Writes `#RRGGBB` for opaque colors and `#RRGGBBAA` otherwise.
+struct Hex(Color);
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b, a] = self.0.into_rgba();
+        if a == 255 {
+            write!(f, "#{r:02X}{g:02X}{b:02X}")
+        } else {
+            write!(f, "#{r:02X}{g:02X}{b:02X}{a:02X}")
+        }
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(&Hex(*self));
+        }
+
+        let mut tup = serializer.serialize_tuple(4)?;
+        for channel in self.into_rgba() {
+            tup.serialize_element(&channel)?;
+        }
+        tup.end()
+    }
+}
+
+struct ColorVisitor;
+
+impl<'de> Visitor<'de> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a color string, an [r, g, b(, a)] array, or an {r, g, b(, a)} map")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Color, E> {
+        parse_color(v).map_err(|err| E::custom(format_args!("invalid color {v:?}: {err}")))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Color, A::Error> {
+        let mut rgba = [0, 0, 0, 255];
+        for (i, channel) in rgba.iter_mut().take(3).enumerate() {
+            *channel = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if let Some(a) = seq.next_element()? {
+            rgba[3] = a;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(5, &self));
+        }
+        Ok(Color::from_rgba(rgba))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Color, A::Error> {
+        const FIELDS: &[&str] = &["r", "g", "b", "a"];
+
+        let mut rgba: [Option<u8>; 4] = [None, None, None, None];
+        while let Some(key) = map.next_key::<ChannelKey>()? {
+            let slot = &mut rgba[key.0];
+            if slot.is_some() {
+                return Err(de::Error::duplicate_field(FIELDS[key.0]));
+            }
+            *slot = Some(map.next_value()?);
+        }
+
+        let channel = |i: usize| rgba[i].ok_or_else(|| de::Error::missing_field(FIELDS[i]));
+        Ok(Color::new(
+            channel(0)?,
+            channel(1)?,
+            channel(2)?,
+            rgba[3].unwrap_or(255),
+        ))
+    }
+}
+
+/// A map key naming one channel (`r`, `g`, `b`, or `a`), stored as its index.
+struct ChannelKey(usize);
+
+impl<'de> Deserialize<'de> for ChannelKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl Visitor<'_> for KeyVisitor {
+            type Value = ChannelKey;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("one of `r`, `g`, `b`, `a`")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<ChannelKey, E> {
+                match v {
+                    "r" => Ok(ChannelKey(0)),
+                    "g" => Ok(ChannelKey(1)),
+                    "b" => Ok(ChannelKey(2)),
+                    "a" => Ok(ChannelKey(3)),
+                    _ => Err(E::unknown_field(v, &["r", "g", "b", "a"])),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(KeyVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(ColorVisitor)
+        } else {
+            deserializer.deserialize_tuple(4, ColorVisitor)
+        }
+    }
+}
+
+impl Serialize for BlendMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for BlendMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BlendModeVisitor;
+
+        impl Visitor<'_> for BlendModeVisitor {
+            type Value = BlendMode;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a kebab-case blend mode name like \"color-dodge\"")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<BlendMode, E> {
+                const NAMES: [&str; 16] = {
+                    let mut names = [""; 16];
+                    let mut i = 0;
+                    while i < names.len() {
+                        names[i] = BlendMode::ALL[i].as_str();
+                        i += 1;
+                    }
+                    names
+                };
+
+                v.parse().map_err(|_| E::unknown_variant(v, &NAMES))
+            }
+        }
+
+        deserializer.deserialize_str(BlendModeVisitor)
+    }
+}
+
+impl Serialize for CompositeOp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CompositeOp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CompositeOpVisitor;
+
+        impl Visitor<'_> for CompositeOpVisitor {
+            type Value = CompositeOp;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a kebab-case compositing operator name like \"source-over\"")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<CompositeOp, E> {
+                const NAMES: [&str; 12] = {
+                    let mut names = [""; 12];
+                    let mut i = 0;
+                    while i < names.len() {
+                        names[i] = CompositeOp::ALL[i].as_str();
+                        i += 1;
+                    }
+                    names
+                };
+
+                v.parse().map_err(|_| E::unknown_variant(v, &NAMES))
+            }
+        }
+
+        deserializer.deserialize_str(CompositeOpVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(json: &str) -> Color {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn color_serializes_as_hex() {
+        let coral = Color::new(255, 127, 80, 255);
+        assert_eq!(serde_json::to_string(&coral).unwrap(), r##""#FF7F50""##);
+        let veil = Color::new(255, 127, 80, 128);
+        assert_eq!(serde_json::to_string(&veil).unwrap(), r##""#FF7F5080""##);
+
+        for c in [coral, veil, Color::TRANSPARENT] {
+            let json = serde_json::to_string(&c).unwrap();
+            assert_eq!(color(&json), c);
+        }
+    }
+
+    #[test]
+    fn color_from_strings() {
+        let coral = Color::new(255, 127, 80, 255);
+        assert_eq!(color(r##""#ff7f50""##), coral);
+        assert_eq!(color(r#""coral""#), coral);
+        assert_eq!(color(r#""rgb(255 127 80)""#), coral);
+        assert_eq!(
+            color(r#""hsl(0 100% 50% / 0.5)""#),
+            Color::new(255, 0, 0, 128)
+        );
+        assert!(serde_json::from_str::<Color>(r#""not-a-color""#).is_err());
+    }
+
+    #[test]
+    fn color_from_arrays() {
+        assert_eq!(color("[1, 2, 3]"), Color::new(1, 2, 3, 255));
+        assert_eq!(color("[1, 2, 3, 4]"), Color::new(1, 2, 3, 4));
+        for json in [
+            "[]",
+            "[1, 2]",
+            "[1, 2, 3, 4, 5]",
+            "[1, 2, 256]",
+            "[1, 2, -1]",
+        ] {
+            assert!(serde_json::from_str::<Color>(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn color_from_maps() {
+        assert_eq!(
+            color(r#"{"r": 1, "g": 2, "b": 3}"#),
+            Color::new(1, 2, 3, 255)
+        );
+        assert_eq!(
+            color(r#"{"a": 4, "b": 3, "g": 2, "r": 1}"#),
+            Color::new(1, 2, 3, 4)
+        );
+        for json in [
+            r#"{"r": 1, "g": 2}"#,
+            r#"{"r": 1, "g": 2, "b": 3, "x": 4}"#,
+            r#"{"r": 1, "r": 1, "g": 2, "b": 3}"#,
+        ] {
+            assert!(serde_json::from_str::<Color>(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn blend_mode_uses_kebab_case_names() {
+        assert_eq!(
+            serde_json::to_string(&BlendMode::ColorDodge).unwrap(),
+            r#""color-dodge""#
+        );
+        assert_eq!(
+            serde_json::from_str::<BlendMode>(r#""soft-light""#).unwrap(),
+            BlendMode::SoftLight
+        );
+        for mode in BlendMode::ALL {
+            let json = serde_json::to_string(&mode).unwrap();
+            assert_eq!(serde_json::from_str::<BlendMode>(&json).unwrap(), mode);
+        }
+        assert!(serde_json::from_str::<BlendMode>(r#""ColorDodge""#).is_err());
+    }
+
+    #[test]
+    fn composite_op_uses_kebab_case_names() {
+        assert_eq!(
+            serde_json::to_string(&CompositeOp::DestinationOut).unwrap(),
+            r#""destination-out""#
+        );
+        for op in CompositeOp::ALL {
+            let json = serde_json::to_string(&op).unwrap();
+            assert_eq!(serde_json::from_str::<CompositeOp>(&json).unwrap(), op);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+#![allow(dead_code)]
+
+// Color temperature: the color of an ideal blackbody radiator, and white balance shifts
+// between two temperatures using the Bradford chromatic adaptation transform.
+//
+// blackbody chromaticities use Krystek's rational approximation of the Planckian locus,
+// which is within about 1e-4 in CIE 1960 uv from 1000K to 15000K and drifts by a few
+// thousandths at most above that, where blue-whites barely differ anyway.
+// source: M. Krystek, "An algorithm to calculate correlated colour temperature" (1985)
+
+use crate::color::{
+    ColorFloat,
+    gamut::{self, LINEAR_SRGB_TO_XYZ_D65, Mat3, XYZ_D65_TO_LINEAR_SRGB},
+    model::Color,
+};
+
+/// The supported temperature range, in kelvin.
+const MIN_KELVIN: ColorFloat = 1000.0;
+const MAX_KELVIN: ColorFloat = 40000.0;
+
+/// Bradford cone response matrix.
+const BRADFORD: Mat3 = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+const BRADFORD_INV: Mat3 = [
+    [0.9869929, -0.1470543, 0.1599627],
+    [0.4323053, 0.5183603, 0.0492912],
+    [-0.0085287, 0.0400428, 0.9684867],
+];
+
+impl Color {
+    /// Create the color of a blackbody radiator at a temperature.
+    ///
+    /// The result is as bright as possible (its largest linear channel is 1.0).
+    /// Very warm temperatures fall just outside sRGB and are gamut mapped.
+    /// For reference, candle light is about 1900K, incandescent bulbs about 2700K,
+    /// daylight about 5500K-6500K, and clear blue sky 10000K and up.
+    ///
+    /// # Arguments
+    ///
+    /// - `kelvin` (`ColorFloat`) - The temperature.
+    ///   This value will be clamped between and including 1000.0 and 40000.0.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The new color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// let tungsten = Color::from_kelvin(2700.0);
+    /// ```
+    #[must_use]
+    pub fn from_kelvin(kelvin: ColorFloat) -> Self {
+        let xyz = blackbody_xyz(kelvin);
+        let lin = gamut::mul3(&XYZ_D65_TO_LINEAR_SRGB, xyz);
+        let max = lin[0].max(lin[1]).max(lin[2]);
+        Self::from_xyz_d65(xyz.map(|v| v / max))
+    }
+
+    /// Shift a color's white balance from one color temperature to another.
+    ///
+    /// Uses Bradford chromatic adaptation in linear light, so a surface that looked
+    /// white under `from_kelvin` light looks white under `to_kelvin` light. Going to
+    /// a lower temperature makes colors warmer, going higher makes them cooler.
+    /// Bright colors can be pushed past 1.0 and clip. Alpha is kept.
+    ///
+    /// # Arguments
+    ///
+    /// - `self` (`Color`) - The color to adjust.
+    /// - `from_kelvin` (`ColorFloat`) - The temperature of the current white.
+    /// - `to_kelvin` (`ColorFloat`) - The temperature of the new white.
+    ///   Both values will be clamped between and including 1000.0 and 40000.0.
+    ///
+    /// # Returns
+    ///
+    /// - `Self` - The adjusted color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codimate::color::Color;
+    ///
+    /// // golden hour
+    /// let sky = Color::new(135, 206, 235, 255);
+    /// let warm_sky = sky.white_balance(6500.0, 4000.0);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn white_balance(self, from_kelvin: ColorFloat, to_kelvin: ColorFloat) -> Self {
+        self.transform_linear(&white_balance_matrix(from_kelvin, to_kelvin))
+    }
+}
+
+/// The linear sRGB matrix that adapts the `from_kelvin` white to the `to_kelvin` white.
+#[allow(clippy::unnecessary_cast)] // only unnecessary with color_double_precision
+pub(crate) fn white_balance_matrix(
+    from_kelvin: ColorFloat,
+    to_kelvin: ColorFloat,
+) -> [[ColorFloat; 3]; 3] {
+    let src = gamut::mul3(&BRADFORD, blackbody_xyz(from_kelvin));
+    let dst = gamut::mul3(&BRADFORD, blackbody_xyz(to_kelvin));
+
+    // scale each cone response from the source white to the destination white
+    let mut scale: Mat3 = [[0.0; 3]; 3];
+    for i in 0..3 {
+        scale[i][i] = (dst[i] / src[i]) as f64;
+    }
+
+    let adapt = gamut::mat_mul(&BRADFORD_INV, &gamut::mat_mul(&scale, &BRADFORD));
+    let m = gamut::mat_mul(
+        &XYZ_D65_TO_LINEAR_SRGB,
+        &gamut::mat_mul(&adapt, &LINEAR_SRGB_TO_XYZ_D65),
+    );
+    m.map(|row| row.map(|v| v as ColorFloat))
+}
+
+/// The CIE XYZ of a blackbody at a temperature, scaled to Y = 1.0.
+#[allow(clippy::unnecessary_cast)] // only unnecessary with color_double_precision
+fn blackbody_xyz(kelvin: ColorFloat) -> [ColorFloat; 3] {
+    let t = kelvin.clamp(MIN_KELVIN, MAX_KELVIN) as f64;
+    let t2 = t * t;
+
+    // Planckian locus in CIE 1960 uv
+    let u = (0.860117757 + 1.54118254e-4 * t + 1.28641212e-7 * t2)
+        / (1.0 + 8.42420235e-4 * t + 7.08145163e-7 * t2);
+    let v = (0.317398726 + 4.22806245e-5 * t + 4.20481691e-8 * t2)
+        / (1.0 - 2.89741816e-5 * t + 1.61456053e-7 * t2);
+
+    // uv -> xy -> XYZ with Y = 1
+    let d = 2.0 * u - 8.0 * v + 4.0;
+    let (x, y) = (3.0 * u / d, 2.0 * v / d);
+    [
+        (x / y) as ColorFloat,
+        1.0,
+        ((1.0 - x - y) / y) as ColorFloat,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Red over blue, kept finite for the reddest temperatures where blue is 0.
+    fn red_blue_ratio(c: Color) -> ColorFloat {
+        let [r, _, b, _] = c.into_rgba();
+        (r as ColorFloat + 1.0) / (b as ColorFloat + 1.0)
+    }
+
+    #[test]
+    fn warmer_is_redder() {
+        let mut previous = ColorFloat::INFINITY;
+        for kelvin in (1000..=40000).step_by(250) {
+            let ratio = red_blue_ratio(Color::from_kelvin(kelvin as ColorFloat));
+            assert!(ratio <= previous, "{kelvin}K");
+            previous = ratio;
+        }
+        for (warm, cool) in [
+            (1900.0, 2700.0),
+            (2700.0, 5000.0),
+            (5000.0, 9000.0),
+            (9000.0, 20000.0),
+        ] {
+            let (warm, cool) = (Color::from_kelvin(warm), Color::from_kelvin(cool));
+            assert!(
+                red_blue_ratio(warm) > red_blue_ratio(cool),
+                "{warm:?} {cool:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn out_of_range_temperatures_clamp() {
+        assert_eq!(Color::from_kelvin(0.0), Color::from_kelvin(MIN_KELVIN));
+        assert_eq!(Color::from_kelvin(-500.0), Color::from_kelvin(MIN_KELVIN));
+        assert_eq!(Color::from_kelvin(1e6), Color::from_kelvin(MAX_KELVIN));
+
+        let c = Color::new(200, 100, 50, 255);
+        assert_eq!(
+            c.white_balance(100.0, 6500.0),
+            c.white_balance(MIN_KELVIN, 6500.0)
+        );
+        assert_eq!(
+            c.white_balance(3000.0, 1e6),
+            c.white_balance(3000.0, MAX_KELVIN)
+        );
+    }
+
+    #[test]
+    fn daylight_is_near_white() {
+        let [r, g, b, a] = Color::from_kelvin(6500.0).into_rgba();
+        assert!(r >= 245 && g >= 245 && b >= 245, "{r} {g} {b}");
+        assert_eq!(a, 255);
+    }
+
+    #[test]
+    fn white_balance_to_the_same_temperature_is_the_identity() {
+        for kelvin in [1000.0, 2700.0, 6500.0, 12000.0, 40000.0] {
+            for r in (0..=255u8).step_by(51) {
+                for g in (0..=255u8).step_by(51) {
+                    for b in (0..=255u8).step_by(51) {
+                        let c = Color::new(r, g, b, 128);
+                        assert_eq!(c.white_balance(kelvin, kelvin), c, "{kelvin}K");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn white_balance_maps_the_source_white_to_the_destination_white() {
+        // both whites are scaled to their brightest channel, so compare the balance, not the level
+        let balance = |c: Color| {
+            let [r, g, b, _] = c.into_rgba().map(|v| v as ColorFloat);
+            [r / b, g / b]
+        };
+        let adapted = balance(Color::from_kelvin(3000.0).white_balance(3000.0, 6500.0));
+        let daylight = balance(Color::from_kelvin(6500.0));
+        for (a, d) in adapted.iter().zip(daylight) {
+            assert!((a - d).abs() < 0.03, "{adapted:?} vs {daylight:?}");
+        }
+    }
+}
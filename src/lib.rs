@@ -1,5 +1,33 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
 use pyo3::prelude::*;
 
-mod color;
-mod renderer;
+#[cfg(feature = "std")]
+mod bindings;
+pub mod color;
+#[cfg(feature = "std")]
+pub mod renderer;
+#[cfg(feature = "std")]
 mod traits;
+
+/// The codimate python module.
+#[cfg(feature = "std")]
+#[pymodule]
+fn codimate(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    use bindings::{color::*, renderer::*};
+
+    m.add_class::<PyColor>()?;
+    m.add_class::<PyBlendMode>()?;
+    m.add_class::<PyCompositeOp>()?;
+    m.add_class::<PyColorVisionDeficiency>()?;
+    m.add_class::<PyPixelFormat>()?;
+    m.add_class::<PyYuvRange>()?;
+    m.add_class::<PyFrame>()?;
+    m.add_class::<PyFrameView>()?;
+    m.add_class::<PyRenderer>()?;
+    m.add_function(wrap_pyfunction!(py_parse_color, m)?)?;
+    m.add("ColorParseError", m.py().get_type::<PyColorParseError>())?;
+    m.add("FrameError", m.py().get_type::<PyFrameError>())?;
+    Ok(())
+}
@@ -0,0 +1,413 @@
+#![allow(dead_code)]
+
+// pixel format conversions between frames (always rgba while drawing) and the layouts
+// encoders want. yuv uses the bt.709 matrix on the gamma encoded values like every hd
+// video pipeline, in 16.16 fixed point so there's no float math per pixel.
+// source: https://www.itu.int/rec/R-REC-BT.709
+
+use core::fmt;
+
+/// The value range of YUV samples.
+///
+/// # Variants
+///
+/// - `Limited` - "TV" range: Y in 16-235 and chroma in 16-240. What almost all
+///   video uses, and what players assume when nothing says otherwise.
+///
+/// - `Full` - "PC" (or JPEG) range: every component uses 0-255.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum YuvRange {
+    #[default]
+    Limited,
+    Full,
+}
+
+/// A layout for a frame's pixel bytes.
+///
+/// # Variants
+///
+/// - `Rgba8888` - 4 bytes per pixel, R G B A. How frames store pixels.
+///
+/// - `Rgb24` - 3 bytes per pixel, R G B. Alpha is dropped.
+///
+/// - `Bgra8888` - 4 bytes per pixel, B G R A.
+///
+/// - `Yuv420p` - Planar BT.709 YUV 4:2:0: a full size Y plane, then U (Cb) and V (Cr)
+///   planes at half the width and height (rounded up), each averaging a 2x2 block.
+///   Alpha is dropped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    #[default]
+    Rgba8888,
+    Rgb24,
+    Bgra8888,
+    Yuv420p(YuvRange),
+}
+
+impl PixelFormat {
+    /// The number of bytes a `width` x `height` image takes in this format.
+    pub const fn buffer_len(self, width: u32, height: u32) -> usize {
+        let (w, h) = (width as usize, height as usize);
+        match self {
+            PixelFormat::Rgba8888 | PixelFormat::Bgra8888 => w * h * 4,
+            PixelFormat::Rgb24 => w * h * 3,
+            PixelFormat::Yuv420p(_) => w * h + 2 * w.div_ceil(2) * h.div_ceil(2),
+        }
+    }
+
+    /// The ffmpeg `-pix_fmt` name of this format.
+    ///
+    /// Both YUV ranges are `"yuv420p"`; pass `-color_range pc` for full range.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            PixelFormat::Rgba8888 => "rgba",
+            PixelFormat::Rgb24 => "rgb24",
+            PixelFormat::Bgra8888 => "bgra",
+            PixelFormat::Yuv420p(_) => "yuv420p",
+        }
+    }
+}
+
+impl fmt::Display for PixelFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// --- bt.709 fixed point --- //
+
+const KR: f64 = 0.2126;
+const KB: f64 = 0.0722;
+const KG: f64 = 1.0 - KR - KB;
+
+/// Fixed point fraction bits.
+const SHIFT: u32 = 16;
+const HALF: i32 = 1 << (SHIFT - 1);
+
+/// Round a coefficient to 16.16 fixed point.
+const fn fix(x: f64) -> i32 {
+    let scaled = x * (1 << SHIFT) as f64;
+    if scaled >= 0.0 {
+        (scaled + 0.5) as i32
+    } else {
+        (scaled - 0.5) as i32
+    }
+}
+
+/// RGB -> YCbCr rows, with the range scaling folded in, plus the Y and chroma offsets.
+struct Forward {
+    y: [i32; 3],
+    cb: [i32; 3],
+    cr: [i32; 3],
+    y_offset: i32,
+}
+
+/// YCbCr -> RGB factors: the Y scale, then Cr for R, Cb and Cr for G, and Cb for B.
+struct Inverse {
+    y: i32,
+    r_cr: i32,
+    g_cb: i32,
+    g_cr: i32,
+    b_cb: i32,
+    y_offset: i32,
+}
+
+const fn forward(range: YuvRange) -> Forward {
+    let (ys, cs, y_offset) = match range {
+        YuvRange::Limited => (219.0 / 255.0, 224.0 / 255.0, 16),
+        YuvRange::Full => (1.0, 1.0, 0),
+    };
+    let cb = 0.5 / (1.0 - KB);
+    let cr = 0.5 / (1.0 - KR);
+    Forward {
+        y: [fix(KR * ys), fix(KG * ys), fix(KB * ys)],
+        cb: [fix(-KR * cb * cs), fix(-KG * cb * cs), fix(0.5 * cs)],
+        cr: [fix(0.5 * cs), fix(-KG * cr * cs), fix(-KB * cr * cs)],
+        y_offset,
+    }
+}
+
+const fn inverse(range: YuvRange) -> Inverse {
+    let (ys, cs, y_offset) = match range {
+        YuvRange::Limited => (255.0 / 219.0, 255.0 / 224.0, 16),
+        YuvRange::Full => (1.0, 1.0, 0),
+    };
+    Inverse {
+        y: fix(ys),
+        r_cr: fix(2.0 * (1.0 - KR) * cs),
+        g_cb: fix(-2.0 * (1.0 - KB) * KB / KG * cs),
+        g_cr: fix(-2.0 * (1.0 - KR) * KR / KG * cs),
+        b_cb: fix(2.0 * (1.0 - KB) * cs),
+        y_offset,
+    }
+}
+
+const FORWARD_LIMITED: Forward = forward(YuvRange::Limited);
+const FORWARD_FULL: Forward = forward(YuvRange::Full);
+const INVERSE_LIMITED: Inverse = inverse(YuvRange::Limited);
+const INVERSE_FULL: Inverse = inverse(YuvRange::Full);
+
+#[inline(always)]
+fn clamp_u8(v: i32) -> u8 {
+    v.clamp(0, 255) as u8
+}
+
+// --- rgba -> other formats --- //
+
+/// Convert tightly packed RGBA8888 pixels into `format`.
+///
+/// `out` must be exactly `format.buffer_len(width, height)` bytes.
+pub(crate) fn from_rgba(rgba: &[u8], width: u32, height: u32, format: PixelFormat, out: &mut [u8]) {
+    debug_assert_eq!(rgba.len(), PixelFormat::Rgba8888.buffer_len(width, height));
+    debug_assert_eq!(out.len(), format.buffer_len(width, height));
+
+    match format {
+        PixelFormat::Rgba8888 => out.copy_from_slice(rgba),
+        PixelFormat::Rgb24 => {
+            for (src, dst) in rgba.chunks_exact(4).zip(out.chunks_exact_mut(3)) {
+                dst.copy_from_slice(&src[..3]);
+            }
+        }
+        PixelFormat::Bgra8888 => {
+            for (src, dst) in rgba.chunks_exact(4).zip(out.chunks_exact_mut(4)) {
+                dst.copy_from_slice(&[src[2], src[1], src[0], src[3]]);
+            }
+        }
+        PixelFormat::Yuv420p(range) => rgba_to_yuv420p(rgba, width, height, range, out),
+    }
+}
+
+fn rgba_to_yuv420p(rgba: &[u8], width: u32, height: u32, range: YuvRange, out: &mut [u8]) {
+    let f = match range {
+        YuvRange::Limited => &FORWARD_LIMITED,
+        YuvRange::Full => &FORWARD_FULL,
+    };
+    let (w, h) = (width as usize, height as usize);
+    let (cw, ch) = (w.div_ceil(2), h.div_ceil(2));
+    let (y_plane, chroma) = out.split_at_mut(w * h);
+    let (u_plane, v_plane) = chroma.split_at_mut(cw * ch);
+
+    // luma, one sample per pixel
+    let y_offset = (f.y_offset << SHIFT) + HALF;
+    for (px, y) in rgba.chunks_exact(4).zip(y_plane.iter_mut()) {
+        let (r, g, b) = (px[0] as i32, px[1] as i32, px[2] as i32);
+        *y = clamp_u8((f.y[0] * r + f.y[1] * g + f.y[2] * b + y_offset) >> SHIFT);
+    }
+
+    // chroma, one sample per 2x2 block. edge blocks on odd sizes have fewer pixels,
+    // so their sums are scaled up to 4 pixels' worth before the shared divide by 4
+    let c_offset = (128 << (SHIFT + 2)) + (HALF << 2);
+    for cy in 0..ch {
+        let rows = if cy * 2 + 1 < h { 2 } else { 1 };
+        for cx in 0..cw {
+            let cols = if cx * 2 + 1 < w { 2 } else { 1 };
+            let (mut r, mut g, mut b) = (0, 0, 0);
+            for dy in 0..rows {
+                let row = (cy * 2 + dy) * w;
+                for dx in 0..cols {
+                    let i = (row + cx * 2 + dx) * 4;
+                    r += rgba[i] as i32;
+                    g += rgba[i + 1] as i32;
+                    b += rgba[i + 2] as i32;
+                }
+            }
+            let scale = 4 / (rows * cols) as i32;
+            let (r, g, b) = (r * scale, g * scale, b * scale);
+
+            let i = cy * cw + cx;
+            u_plane[i] =
+                clamp_u8((f.cb[0] * r + f.cb[1] * g + f.cb[2] * b + c_offset) >> (SHIFT + 2));
+            v_plane[i] =
+                clamp_u8((f.cr[0] * r + f.cr[1] * g + f.cr[2] * b + c_offset) >> (SHIFT + 2));
+        }
+    }
+}
+
+// --- other formats -> rgba --- //
+
+/// Convert pixels in `format` into tightly packed RGBA8888.
+///
+/// `data` must be exactly `format.buffer_len(width, height)` bytes. Formats
+/// without alpha come out opaque.
+pub(crate) fn to_rgba(data: &[u8], width: u32, height: u32, format: PixelFormat, rgba: &mut [u8]) {
+    debug_assert_eq!(data.len(), format.buffer_len(width, height));
+    debug_assert_eq!(rgba.len(), PixelFormat::Rgba8888.buffer_len(width, height));
+
+    match format {
+        PixelFormat::Rgba8888 => rgba.copy_from_slice(data),
+        PixelFormat::Rgb24 => {
+            for (src, dst) in data.chunks_exact(3).zip(rgba.chunks_exact_mut(4)) {
+                dst.copy_from_slice(&[src[0], src[1], src[2], 255]);
+            }
+        }
+        PixelFormat::Bgra8888 => {
+            for (src, dst) in data.chunks_exact(4).zip(rgba.chunks_exact_mut(4)) {
+                dst.copy_from_slice(&[src[2], src[1], src[0], src[3]]);
+            }
+        }
+        PixelFormat::Yuv420p(range) => yuv420p_to_rgba(data, width, height, range, rgba),
+    }
+}
+
+fn yuv420p_to_rgba(data: &[u8], width: u32, height: u32, range: YuvRange, rgba: &mut [u8]) {
+    let k = match range {
+        YuvRange::Limited => &INVERSE_LIMITED,
+        YuvRange::Full => &INVERSE_FULL,
+    };
+    let (w, h) = (width as usize, height as usize);
+    let cw = w.div_ceil(2);
+    let (y_plane, chroma) = data.split_at(w * h);
+    let (u_plane, v_plane) = chroma.split_at(cw * h.div_ceil(2));
+
+    // chroma is upsampled nearest neighbor: each sample covers its 2x2 block
+    let rows = y_plane.chunks_exact(w).zip(rgba.chunks_exact_mut(w * 4));
+    for (y, (luma_row, out_row)) in rows.enumerate() {
+        let chroma_row = (y / 2) * cw;
+        let u_row = &u_plane[chroma_row..chroma_row + cw];
+        let v_row = &v_plane[chroma_row..chroma_row + cw];
+        for (x, (&luma, px)) in luma_row.iter().zip(out_row.chunks_exact_mut(4)).enumerate() {
+            let luma = k.y * (luma as i32 - k.y_offset) + HALF;
+            let cb = u_row[x / 2] as i32 - 128;
+            let cr = v_row[x / 2] as i32 - 128;
+
+            px[0] = clamp_u8((luma + k.r_cr * cr) >> SHIFT);
+            px[1] = clamp_u8((luma + k.g_cb * cb + k.g_cr * cr) >> SHIFT);
+            px[2] = clamp_u8((luma + k.b_cb * cb) >> SHIFT);
+            px[3] = 255;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RANGES: [YuvRange; 2] = [YuvRange::Limited, YuvRange::Full];
+
+    /// A small xorshift so the images are varied but reproducible.
+    fn noise(seed: &mut u32) -> u8 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 17;
+        *seed ^= *seed << 5;
+        (*seed >> 24) as u8
+    }
+
+    /// An opaque image where every 2x2 chroma block is one random color,
+    /// so 4:2:0 subsampling loses nothing but rounding.
+    fn blocky(width: u32, height: u32, seed: &mut u32) -> Vec<u8> {
+        let (w, h) = (width as usize, height as usize);
+        let colors: Vec<[u8; 4]> = (0..w.div_ceil(2) * h.div_ceil(2))
+            .map(|_| [noise(seed), noise(seed), noise(seed), 255])
+            .collect();
+        (0..w * h)
+            .flat_map(|i| colors[(i / w / 2) * w.div_ceil(2) + (i % w) / 2])
+            .collect()
+    }
+
+    fn convert(rgba: &[u8], width: u32, height: u32, format: PixelFormat) -> Vec<u8> {
+        let mut out = vec![0; format.buffer_len(width, height)];
+        from_rgba(rgba, width, height, format, &mut out);
+        out
+    }
+
+    fn back(data: &[u8], width: u32, height: u32, format: PixelFormat) -> Vec<u8> {
+        let mut rgba = vec![0; PixelFormat::Rgba8888.buffer_len(width, height)];
+        to_rgba(data, width, height, format, &mut rgba);
+        rgba
+    }
+
+    #[test]
+    fn buffer_len_rounds_chroma_up() {
+        let yuv = PixelFormat::Yuv420p(YuvRange::Limited);
+        assert_eq!(yuv.buffer_len(1920, 1080), 1920 * 1080 * 3 / 2);
+        assert_eq!(yuv.buffer_len(3, 5), 15 + 2 * 2 * 3);
+        assert_eq!(yuv.buffer_len(1, 1), 3);
+        assert_eq!(PixelFormat::Rgb24.buffer_len(3, 5), 45);
+        assert_eq!(PixelFormat::Bgra8888.buffer_len(3, 5), 60);
+    }
+
+    #[test]
+    fn packed_formats_round_trip() {
+        let rgba: Vec<u8> = (0..=255).collect();
+        let bgra = convert(&rgba, 8, 8, PixelFormat::Bgra8888);
+        assert_eq!(&bgra[..8], &[2, 1, 0, 3, 6, 5, 4, 7]);
+        assert_eq!(back(&bgra, 8, 8, PixelFormat::Bgra8888), rgba);
+
+        let rgb = convert(&rgba, 8, 8, PixelFormat::Rgb24);
+        assert_eq!(&rgb[..6], &[0, 1, 2, 4, 5, 6]);
+        let opaque = back(&rgb, 8, 8, PixelFormat::Rgb24);
+        for (px, orig) in opaque.chunks(4).zip(rgba.chunks(4)) {
+            assert_eq!(px, [orig[0], orig[1], orig[2], 255]);
+        }
+    }
+
+    #[test]
+    fn yuv_anchors() {
+        let limited = PixelFormat::Yuv420p(YuvRange::Limited);
+        let full = PixelFormat::Yuv420p(YuvRange::Full);
+        let white = [255; 16];
+        let black: Vec<u8> = [0, 0, 0, 255].repeat(4);
+
+        assert_eq!(
+            convert(&white, 2, 2, limited),
+            [235, 235, 235, 235, 128, 128]
+        );
+        assert_eq!(convert(&black, 2, 2, limited), [16, 16, 16, 16, 128, 128]);
+        assert_eq!(convert(&white, 2, 2, full), [255, 255, 255, 255, 128, 128]);
+        assert_eq!(convert(&black, 2, 2, full), [0, 0, 0, 0, 128, 128]);
+
+        assert_eq!(back(&[235, 235, 235, 235, 128, 128], 2, 2, limited), white);
+        assert_eq!(back(&[16, 16, 16, 16, 128, 128], 2, 2, limited), black);
+
+        // bt.709 red is Y 63, Cb 102, Cr 240 in limited range
+        let red: Vec<u8> = [255, 0, 0, 255].repeat(4);
+        assert_eq!(convert(&red, 2, 2, limited), [63, 63, 63, 63, 102, 240]);
+    }
+
+    #[test]
+    fn yuv_round_trip_is_within_2() {
+        let mut seed = 0x1234_5678;
+        for range in RANGES {
+            let format = PixelFormat::Yuv420p(range);
+            for (w, h) in [(2, 2), (16, 8), (1, 1), (1, 7), (7, 1), (5, 3), (33, 17)] {
+                let rgba = blocky(w, h, &mut seed);
+                let out = back(&convert(&rgba, w, h, format), w, h, format);
+                for (i, (a, b)) in out.iter().zip(&rgba).enumerate() {
+                    assert!(
+                        a.abs_diff(*b) <= 2,
+                        "{range:?} {w}x{h} byte {i}: {a} vs {b}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn yuv_edge_blocks_on_odd_sizes() {
+        let mut seed = 0x0bad_cafe;
+        for range in RANGES {
+            let format = PixelFormat::Yuv420p(range);
+            for _ in 0..32 {
+                let color = [noise(&mut seed), noise(&mut seed), noise(&mut seed), 255];
+                let full_block = convert(&color.repeat(4), 2, 2, format);
+                let (u, v) = (full_block[4], full_block[5]);
+
+                // partial blocks on the right and bottom edges must get the same chroma
+                // as a full block of the same color
+                for (w, h) in [(1, 1), (3, 2), (2, 3), (3, 3), (5, 1)] {
+                    let out = convert(&color.repeat((w * h) as usize), w, h, format);
+                    let chroma = &out[(w * h) as usize..];
+                    let (us, vs) = chroma.split_at(chroma.len() / 2);
+                    assert!(
+                        us.iter().all(|&x| x == u),
+                        "{range:?} {w}x{h}: {us:?} != {u}"
+                    );
+                    assert!(
+                        vs.iter().all(|&x| x == v),
+                        "{range:?} {w}x{h}: {vs:?} != {v}"
+                    );
+                }
+            }
+        }
+    }
+}
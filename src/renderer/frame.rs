@@ -1,68 +1,273 @@
 #![allow(dead_code)]
 
-use image::EncodableLayout;
+use core::fmt;
 
-use crate::color::model::Color;
+use crate::{
+    color::{
+        ColorFloat, cvd::ColorVisionDeficiency, model::Color, temperature::white_balance_matrix,
+    },
+    renderer::conversions::{self, PixelFormat},
+};
 
-// frames -> width, height, and the actual color data
+/// The largest width or height a frame can have. 8K is 7680x4320, so this leaves
+/// plenty of room while keeping a frame's byte count (4 GiB at most) sane.
+pub const MAX_DIMENSION: u32 = 32768;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameError {
+    /// The width or height was 0.
+    ZeroDimension { width: u32, height: u32 },
+    /// The width or height was over `MAX_DIMENSION`, or the pixel data wouldn't fit in memory.
+    TooLarge { width: u32, height: u32 },
+    /// A pixel buffer didn't have the size its dimensions and format need.
+    BufferSize { expected: usize, actual: usize },
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FrameError::*;
+        match self {
+            ZeroDimension { width, height } => {
+                write!(f, "frame dimensions must be nonzero, got {width}x{height}")
+            }
+            TooLarge { width, height } => write!(
+                f,
+                "frame dimensions {width}x{height} are too large (max {MAX_DIMENSION}x{MAX_DIMENSION})"
+            ),
+            BufferSize { expected, actual } => {
+                write!(f, "pixel buffer is {actual} bytes, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FrameError {}
+
+// frames -> width, height, and the actual color data.
+// one packed rgba8888 word per pixel (see `Color::pack_rgba`) so kernels write whole
+// u32s instead of 4 byte copies. the bytes of each word in memory are r g b a, so the
+// byte view encoders get from `as_slice` is plain rgba8888 on any endianness
 pub struct Frame {
-    width: u16,
-    height: u16,
-    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    data: Vec<u32>,
 }
 
-// note from noar: i assume we wanna add other color support like
-// rgb24 (3 bytes per pixel) and yuv420 (4:2:0 planar layout). frn just added basic rgba
+// frames always store and draw in rgba8888. other pixel formats (rgb24, bgra, yuv420p)
+// only exist at the edges: to_pixels/write_pixels for encoders and from_pixels for decoders
 impl Frame {
-    pub fn new(width: u16, height: u16) -> Self {
-        let data = vec![0; (width * height * 4).into()];
-        Self {
+    // panics on bad dimensions. use try_new when they come from outside (python, config, etc)
+    pub fn new(width: u32, height: u32) -> Self {
+        match Self::try_new(width, height) {
+            Ok(frame) => frame,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    // fallible constructor. sizes are checked before allocating so a typo'd
+    // resolution gives an error instead of an overflow or a huge allocation
+    pub fn try_new(width: u32, height: u32) -> Result<Self, FrameError> {
+        if width == 0 || height == 0 {
+            return Err(FrameError::ZeroDimension { width, height });
+        }
+        if width > MAX_DIMENSION || height > MAX_DIMENSION {
+            return Err(FrameError::TooLarge { width, height });
+        }
+
+        // usize can be 32 bits, so the byte count still needs checking
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .filter(|&px| px <= isize::MAX as usize / 4)
+            .ok_or(FrameError::TooLarge { width, height })?;
+
+        Ok(Self {
             width,
             height,
-            data,
+            data: vec![0; len],
+        })
+    }
+
+    // builds a frame from pixels in any format, e.g. a decoded yuv420p video frame
+    pub fn from_pixels(
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        data: &[u8],
+    ) -> Result<Self, FrameError> {
+        let mut frame = Self::try_new(width, height)?;
+        let expected = format.buffer_len(width, height);
+        if data.len() != expected {
+            return Err(FrameError::BufferSize {
+                expected,
+                actual: data.len(),
+            });
         }
+
+        conversions::to_rgba(data, width, height, format, frame.as_bytes_mut());
+        Ok(frame)
+    }
+
+    // the frame in another pixel format, e.g. yuv420p to pipe straight into an encoder
+    pub fn to_pixels(&self, format: PixelFormat) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write_pixels(format, &mut out);
+        out
+    }
+
+    // same as to_pixels but reuses `out`, so encoding a whole video doesn't allocate per frame
+    pub fn write_pixels(&self, format: PixelFormat, out: &mut Vec<u8>) {
+        out.resize(format.buffer_len(self.width, self.height), 0);
+        conversions::from_rgba(self.as_slice(), self.width, self.height, format, out);
     }
 
     // resolution accessors. used for getting pixels & rows
-    pub fn width(&self) -> u16 {
+    pub fn width(&self) -> u32 {
         self.width
     }
 
-    pub fn height(&self) -> u16 {
+    pub fn height(&self) -> u32 {
         self.height
     }
 
     // accessor for frame data. ffmpeg will need this to be read only for using stdin
     pub fn as_slice(&self) -> &[u8] {
-        self.data.as_slice()
+        // SAFETY: u32 has no padding and a stricter alignment than u8, so every
+        // word is exactly 4 initialized bytes and the byte slice covers the same memory
+        unsafe { core::slice::from_raw_parts(self.data.as_ptr().cast(), self.data.len() * 4) }
     }
 
     // mutable accessor for frame data. i'm pretty sure we'll need this but my brain hurts so i'll just add it
     pub fn as_bytes_mut(&mut self) -> &mut [u8] {
-        self.data.as_mut_slice()
+        // SAFETY: same as as_slice, and any 4 bytes are a valid u32 so writes can't break anything
+        unsafe {
+            core::slice::from_raw_parts_mut(self.data.as_mut_ptr().cast(), self.data.len() * 4)
+        }
+    }
+
+    // the packed pixels, one word per pixel row by row. what the drawing kernels use
+    pub fn as_words(&self) -> &[u32] {
+        &self.data
+    }
+
+    pub fn as_words_mut(&mut self) -> &mut [u32] {
+        &mut self.data
     }
 
     #[inline]
     // cuz usizes are unsigned we don't have to worry about negative checks
-    fn in_bounds(&self, x: u16, y: u16) -> bool {
+    fn in_bounds(&self, x: u32, y: u32) -> bool {
         x < self.width && y < self.height
     }
 
+    // word index of a pixel. index in usize so big frames can't overflow
+    #[inline]
+    fn offset(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
     // used in blend operations
-    pub fn get_pixel(&self, x: u16, y: u16) -> Option<Color> {
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<Color> {
         if !self.in_bounds(x, y) {
             return None;
         }
 
-        // vectors use usize so this the only way to make the compiler stop yellin
-        // i'll look into this in a little bit
-        // - noah
-        let i: usize = ((y * self.width + x) * 4).into();
-        Some(Color::from_rgba([
-            self.data[i],
-            self.data[i + 1],
-            self.data[i + 2],
-            self.data[i + 3],
-        ]))
+        Some(Color::unpack_rgba(self.data[self.offset(x, y)]))
+    }
+
+    // recolors the whole frame as someone with a color vision deficiency would see it.
+    // handy for previewing a scene under each simulation. severity goes 0.0 (normal) to 1.0
+    pub fn simulate_cvd(&mut self, deficiency: ColorVisionDeficiency, severity: ColorFloat) {
+        // the matrix only depends on the args so build it once, not per pixel
+        let m = deficiency.matrix(severity);
+        for px in self.data.iter_mut() {
+            *px = Color::unpack_rgba(*px).transform_linear(&m).pack_rgba();
+        }
+    }
+
+    // shifts the white balance of the whole frame, e.g. 6500 -> 4000 for a warm sunset look.
+    // same bradford adaptation as `Color::white_balance`, just with the matrix built once
+    pub fn white_balance(&mut self, from_kelvin: ColorFloat, to_kelvin: ColorFloat) {
+        let m = white_balance_matrix(from_kelvin, to_kelvin);
+        for px in self.data.iter_mut() {
+            *px = Color::unpack_rgba(*px).transform_linear(&m).pack_rgba();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_new_accepts_max_dimension() {
+        let wide = Frame::try_new(MAX_DIMENSION, 1).unwrap();
+        assert_eq!((wide.width(), wide.height()), (MAX_DIMENSION, 1));
+        assert_eq!(wide.as_words().len(), MAX_DIMENSION as usize);
+
+        let tall = Frame::try_new(1, MAX_DIMENSION).unwrap();
+        assert_eq!((tall.width(), tall.height()), (1, MAX_DIMENSION));
+    }
+
+    #[test]
+    fn try_new_rejects_bad_dimensions() {
+        let over = MAX_DIMENSION + 1;
+        for (width, height) in [(over, 1), (1, over), (over, over), (u32::MAX, u32::MAX)] {
+            assert_eq!(
+                Frame::try_new(width, height).err(),
+                Some(FrameError::TooLarge { width, height })
+            );
+        }
+        for (width, height) in [(0, 0), (0, 1080), (1920, 0), (0, over)] {
+            assert_eq!(
+                Frame::try_new(width, height).err(),
+                Some(FrameError::ZeroDimension { width, height })
+            );
+        }
+    }
+
+    #[test]
+    fn get_pixel_reaches_the_far_corner_of_4k() {
+        let (w, h) = (3840, 2160);
+        let mut frame = Frame::new(w, h);
+        let corner = Color::new(1, 2, 3, 4);
+        let last = frame.as_words().len() - 1;
+        frame.as_words_mut()[last] = corner.pack_rgba();
+
+        assert_eq!(frame.get_pixel(w - 1, h - 1), Some(corner));
+        assert_eq!(frame.get_pixel(0, h - 1), Some(Color::TRANSPARENT));
+        assert_eq!(frame.get_pixel(w - 1, 0), Some(Color::TRANSPARENT));
+        assert_eq!(frame.get_pixel(w, h - 1), None);
+        assert_eq!(frame.get_pixel(w - 1, h), None);
+    }
+
+    #[test]
+    fn from_pixels_checks_the_buffer_size() {
+        let format = PixelFormat::Yuv420p(conversions::YuvRange::Limited);
+        let expected = format.buffer_len(3, 3);
+        assert_eq!(expected, 9 + 2 * 4);
+
+        for actual in [0, expected - 1, expected + 1] {
+            assert_eq!(
+                Frame::from_pixels(3, 3, format, &vec![0; actual]).err(),
+                Some(FrameError::BufferSize { expected, actual })
+            );
+        }
+        let frame = Frame::from_pixels(3, 3, format, &vec![128; expected]).unwrap();
+        assert_eq!(frame.as_slice().len(), 3 * 3 * 4);
+
+        // the dimensions are checked before the buffer
+        assert_eq!(
+            Frame::from_pixels(0, 3, PixelFormat::Rgba8888, &[]).err(),
+            Some(FrameError::ZeroDimension {
+                width: 0,
+                height: 3
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must be nonzero")]
+    fn new_panics_on_zero_dimension() {
+        let _ = Frame::new(0, 10);
     }
 }
@@ -0,0 +1,394 @@
+#![allow(dead_code)]
+
+// saving and loading frames as still images. png goes through the `image` crate.
+// qoi and ppm are written by hand straight from the frame's pixels, a row at a time,
+// which makes them the fast way to dump frames while debugging a scene.
+// qoi spec: https://qoiformat.org/qoi-specification.pdf
+// ppm spec: https://netpbm.sourceforge.net/doc/ppm.html
+
+use core::fmt;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use image::{ExtendedColorType, ImageEncoder, ImageReader, codecs::png::PngEncoder};
+
+use crate::renderer::{
+    conversions::{self, PixelFormat},
+    frame::{Frame, FrameError},
+};
+
+#[derive(Debug)]
+pub enum ImageIoError {
+    /// Reading or writing the file failed.
+    Io(io::Error),
+    /// The image couldn't be decoded or encoded.
+    Image(image::ImageError),
+    /// The decoded image doesn't fit in a frame.
+    Frame(FrameError),
+}
+
+impl fmt::Display for ImageIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageIoError::Io(err) => write!(f, "image io failed: {err}"),
+            ImageIoError::Image(err) => write!(f, "image codec failed: {err}"),
+            ImageIoError::Frame(err) => write!(f, "image can't be a frame: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ImageIoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImageIoError::Io(err) => Some(err),
+            ImageIoError::Image(err) => Some(err),
+            ImageIoError::Frame(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for ImageIoError {
+    fn from(err: io::Error) -> Self {
+        ImageIoError::Io(err)
+    }
+}
+
+impl From<image::ImageError> for ImageIoError {
+    fn from(err: image::ImageError) -> Self {
+        // the image crate wraps plain io errors too. unwrap them so a missing
+        // file is an io error no matter which step hit it
+        match err {
+            image::ImageError::IoError(err) => ImageIoError::Io(err),
+            err => ImageIoError::Image(err),
+        }
+    }
+}
+
+impl From<FrameError> for ImageIoError {
+    fn from(err: FrameError) -> Self {
+        ImageIoError::Frame(err)
+    }
+}
+
+// --- qoi --- //
+
+const QOI_OP_INDEX: u8 = 0x00;
+const QOI_OP_DIFF: u8 = 0x40;
+const QOI_OP_LUMA: u8 = 0x80;
+const QOI_OP_RUN: u8 = 0xc0;
+const QOI_OP_RGB: u8 = 0xfe;
+const QOI_OP_RGBA: u8 = 0xff;
+/// Runs are stored biased by -1 in 6 bits, and 63 and 64 would collide with the rgb(a) tags.
+const QOI_MAX_RUN: u8 = 62;
+const QOI_END: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+
+#[inline]
+fn qoi_hash([r, g, b, a]: [u8; 4]) -> usize {
+    (r as usize * 3 + g as usize * 5 + b as usize * 7 + a as usize * 11) % 64
+}
+
+impl Frame {
+    // loads any format the image crate knows (png, jpeg, qoi, ppm, ...). the format comes
+    // from the file's contents, not its extension. 16 bit and float images get rounded to 8 bit
+    pub fn from_image(path: impl AsRef<Path>) -> Result<Self, ImageIoError> {
+        let image = ImageReader::open(path)?.with_guessed_format()?.decode()?;
+        Self::from_rgba_image(image.into_rgba8())
+    }
+
+    // same as from_image but for an encoded image already in memory
+    pub fn decode_image(bytes: &[u8]) -> Result<Self, ImageIoError> {
+        Self::from_rgba_image(image::load_from_memory(bytes)?.into_rgba8())
+    }
+
+    fn from_rgba_image(image: image::RgbaImage) -> Result<Self, ImageIoError> {
+        let (width, height) = image.dimensions();
+        Ok(Self::from_pixels(
+            width,
+            height,
+            PixelFormat::Rgba8888,
+            image.as_raw(),
+        )?)
+    }
+
+    // --- png --- //
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), ImageIoError> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_png(&mut out)?;
+        Ok(out.flush()?)
+    }
+
+    pub fn encode_png(&self) -> Result<Vec<u8>, ImageIoError> {
+        let mut out = Vec::new();
+        self.write_png(&mut out)?;
+        Ok(out)
+    }
+
+    pub fn write_png(&self, out: impl Write) -> Result<(), ImageIoError> {
+        PngEncoder::new(out).write_image(
+            self.as_slice(),
+            self.width(),
+            self.height(),
+            ExtendedColorType::Rgba8,
+        )?;
+        Ok(())
+    }
+
+    // --- qoi --- //
+    // lossless like png but encodes way faster, and keeps alpha
+
+    pub fn save_qoi(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_qoi(&mut out)?;
+        out.flush()
+    }
+
+    pub fn encode_qoi(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        // writing into a vec can't fail
+        let _ = self.write_qoi(&mut out);
+        out
+    }
+
+    pub fn write_qoi(&self, mut out: impl Write) -> io::Result<()> {
+        // header: magic, size (big endian), 4 channels, srgb with linear alpha
+        out.write_all(b"qoif")?;
+        out.write_all(&self.width().to_be_bytes())?;
+        out.write_all(&self.height().to_be_bytes())?;
+        out.write_all(&[4, 0])?;
+
+        // ops are collected per row so the writer isn't called per pixel
+        let mut buf = Vec::with_capacity(self.width() as usize * 5 + QOI_END.len());
+        let mut index = [[0u8; 4]; 64];
+        let mut prev = [0, 0, 0, 255];
+        let mut run = 0;
+
+        for row in self.as_slice().chunks_exact(self.width() as usize * 4) {
+            for px in row.chunks_exact(4) {
+                let px = [px[0], px[1], px[2], px[3]];
+                if px == prev {
+                    run += 1;
+                    if run == QOI_MAX_RUN {
+                        buf.push(QOI_OP_RUN | (run - 1));
+                        run = 0;
+                    }
+                    continue;
+                }
+                if run > 0 {
+                    buf.push(QOI_OP_RUN | (run - 1));
+                    run = 0;
+                }
+
+                let hash = qoi_hash(px);
+                if index[hash] == px {
+                    buf.push(QOI_OP_INDEX | hash as u8);
+                } else {
+                    index[hash] = px;
+                    push_qoi_color(&mut buf, px, prev);
+                }
+                prev = px;
+            }
+            out.write_all(&buf)?;
+            buf.clear();
+        }
+
+        // a run can carry across rows, so it's only flushed at the very end
+        if run > 0 {
+            buf.push(QOI_OP_RUN | (run - 1));
+        }
+        buf.extend_from_slice(&QOI_END);
+        out.write_all(&buf)
+    }
+
+    // --- ppm --- //
+    // binary (P6) rgb. alpha is dropped. about as simple as an image format gets,
+    // and most viewers and ffmpeg read it
+
+    pub fn save_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_ppm(&mut out)?;
+        out.flush()
+    }
+
+    pub fn encode_ppm(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        // writing into a vec can't fail
+        let _ = self.write_ppm(&mut out);
+        out
+    }
+
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width(), self.height())?;
+
+        // converted a row at a time so big frames don't need a second full size buffer
+        let width = self.width();
+        let mut rgb = vec![0; PixelFormat::Rgb24.buffer_len(width, 1)];
+        for row in self.as_slice().chunks_exact(width as usize * 4) {
+            conversions::from_rgba(row, width, 1, PixelFormat::Rgb24, &mut rgb);
+            out.write_all(&rgb)?;
+        }
+        Ok(())
+    }
+}
+
+/// Push the smallest QOI op that encodes `px` given the previous pixel.
+#[inline]
+fn push_qoi_color(buf: &mut Vec<u8>, px: [u8; 4], prev: [u8; 4]) {
+    let [r, g, b, a] = px;
+    if a != prev[3] {
+        buf.extend_from_slice(&[QOI_OP_RGBA, r, g, b, a]);
+        return;
+    }
+
+    // channel differences wrap, so 0 - 255 counts as +1
+    let dr = r.wrapping_sub(prev[0]) as i8;
+    let dg = g.wrapping_sub(prev[1]) as i8;
+    let db = b.wrapping_sub(prev[2]) as i8;
+    let dr_dg = dr.wrapping_sub(dg);
+    let db_dg = db.wrapping_sub(dg);
+
+    if (-2..=1).contains(&dr) && (-2..=1).contains(&dg) && (-2..=1).contains(&db) {
+        buf.push(QOI_OP_DIFF | ((dr + 2) as u8) << 4 | ((dg + 2) as u8) << 2 | (db + 2) as u8);
+    } else if (-32..=31).contains(&dg) && (-8..=7).contains(&dr_dg) && (-8..=7).contains(&db_dg) {
+        buf.extend_from_slice(&[
+            QOI_OP_LUMA | (dg + 32) as u8,
+            ((dr_dg + 8) as u8) << 4 | (db_dg + 8) as u8,
+        ]);
+    } else {
+        buf.extend_from_slice(&[QOI_OP_RGB, r, g, b]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::model::Color;
+
+    /// A small xorshift so the frames are varied but reproducible.
+    fn noise(seed: &mut u32) -> u32 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 17;
+        *seed ^= *seed << 5;
+        *seed
+    }
+
+    /// A frame that hits every QOI op: runs (some longer than `QOI_MAX_RUN` and some
+    /// crossing rows), a small palette for index hits, gentle gradients for the
+    /// diff and luma ops, random pixels for rgb, and alpha changes for rgba.
+    fn busy_frame(width: u32, height: u32, seed: &mut u32) -> Frame {
+        let palette = [
+            Color::new(255, 0, 0, 255),
+            Color::new(0, 128, 255, 255),
+            Color::new(20, 20, 20, 128),
+        ];
+        let mut frame = Frame::new(width, height);
+        for (i, px) in frame.as_words_mut().iter_mut().enumerate() {
+            let n = noise(seed);
+            let color = match (i / 97) % 5 {
+                0 => Color::new(10, 20, 30, 255),
+                1 => palette[n as usize % palette.len()],
+                2 => Color::new(i as u8, (i / 2) as u8, 200u8.wrapping_sub(i as u8), 255),
+                3 => Color::new((i * 5) as u8, (i * 3) as u8, (i * 7) as u8, 255),
+                _ => Color::unpack_rgba(n),
+            };
+            *px = color.pack_rgba();
+        }
+        frame
+    }
+
+    fn frames(seed: &mut u32) -> Vec<Frame> {
+        let mut frames: Vec<Frame> = [(1, 1), (1, 300), (300, 1), (7, 5), (64, 48), (130, 3)]
+            .into_iter()
+            .map(|(w, h)| busy_frame(w, h, seed))
+            .collect();
+
+        // long runs of transparent black and of another color, both crossing rows
+        frames.push(Frame::new(100, 10));
+        let mut solid = Frame::new(9, 31);
+        solid
+            .as_words_mut()
+            .fill(Color::new(1, 2, 3, 4).pack_rgba());
+        frames.push(solid);
+        frames
+    }
+
+    #[test]
+    fn qoi_and_png_decode_to_the_same_pixels() {
+        let mut seed = 0xdead_beef;
+        for frame in frames(&mut seed) {
+            let size = (frame.width(), frame.height());
+
+            let qoi = Frame::decode_image(&frame.encode_qoi()).unwrap();
+            assert_eq!((qoi.width(), qoi.height()), size);
+            assert_eq!(qoi.as_slice(), frame.as_slice(), "qoi {size:?}");
+
+            let png = Frame::decode_image(&frame.encode_png().unwrap()).unwrap();
+            assert_eq!((png.width(), png.height()), size);
+            assert_eq!(png.as_slice(), frame.as_slice(), "png {size:?}");
+        }
+    }
+
+    #[test]
+    fn ppm_decodes_to_opaque_rgb() {
+        let mut seed = 0x0123_4567;
+        for frame in frames(&mut seed) {
+            let ppm = frame.encode_ppm();
+            let header = format!("P6\n{} {}\n255\n", frame.width(), frame.height());
+            assert!(ppm.starts_with(header.as_bytes()));
+            assert_eq!(ppm.len(), header.len() + frame.as_words().len() * 3);
+
+            let decoded = Frame::decode_image(&ppm).unwrap();
+            assert_eq!(
+                (decoded.width(), decoded.height()),
+                (frame.width(), frame.height())
+            );
+            for (a, b) in decoded.as_slice().chunks(4).zip(frame.as_slice().chunks(4)) {
+                assert_eq!(a, [b[0], b[1], b[2], 255]);
+            }
+        }
+    }
+
+    #[test]
+    fn qoi_runs_carry_across_rows_and_split_at_max_run() {
+        // a frame of the initial `prev` pixel is nothing but runs: 100 = 62 + 38
+        let mut frame = Frame::new(10, 10);
+        frame.as_words_mut().fill(Color::BLACK.pack_rgba());
+        let qoi = frame.encode_qoi();
+
+        let mut expected = b"qoif".to_vec();
+        expected.extend_from_slice(&10u32.to_be_bytes());
+        expected.extend_from_slice(&10u32.to_be_bytes());
+        expected.extend_from_slice(&[4, 0]);
+        expected.extend_from_slice(&[QOI_OP_RUN | 61, QOI_OP_RUN | 37]);
+        expected.extend_from_slice(&QOI_END);
+        assert_eq!(qoi, expected);
+    }
+
+    #[test]
+    fn push_qoi_color_picks_the_smallest_op() {
+        let op = |px, prev| {
+            let mut buf = Vec::new();
+            push_qoi_color(&mut buf, px, prev);
+            buf
+        };
+        let prev = [100, 100, 100, 255];
+        assert_eq!(
+            op([99, 101, 98, 255], prev),
+            [QOI_OP_DIFF | 1 << 4 | 3 << 2]
+        );
+        assert_eq!(op([115, 110, 105, 255], prev), [QOI_OP_LUMA | 42, 0xd3]);
+        assert_eq!(op([200, 0, 100, 255], prev), [QOI_OP_RGB, 200, 0, 100]);
+        assert_eq!(
+            op([100, 100, 100, 0], prev),
+            [QOI_OP_RGBA, 100, 100, 100, 0]
+        );
+        // differences wrap around 0 and 255
+        assert_eq!(
+            op([0, 0, 0, 255], [255, 255, 255, 255]),
+            [QOI_OP_DIFF | 3 << 4 | 3 << 2 | 3]
+        );
+    }
+}
@@ -1,6 +1,8 @@
+pub mod conversions; // pixel format conversions for encoders
 pub mod frame;
+pub mod io; // saving and loading frames as png, qoi and ppm
 pub mod render; // (frame may end up in here tbh)
+pub mod view; // borrowed sub rectangles of frames
 
 // planned modules
 // pub mod shapes;
-// pub mod conversions; (codec conversions)
\ No newline at end of file
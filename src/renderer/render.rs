@@ -1,8 +1,11 @@
 #![allow(dead_code)]
 
-/* ik this is jank so i'm gonna figure out how to modularize so 
+/* ik this is jank so i'm gonna figure out how to modularize so
 every submodule that's a part of src/folder is prefixed by crate::folder */
-use crate::{color::model::Color, renderer::frame::Frame};
+use crate::{
+    color::model::Color,
+    renderer::{frame::Frame, view::FrameViewMut},
+};
 
 pub struct Renderer {
     current: usize,
@@ -12,10 +15,7 @@ pub struct Renderer {
 impl Renderer {
     // doing queue based on index now
     pub fn new(queue: Vec<Frame>) -> Self {
-        Self {
-            current: 0,
-            queue,
-        }
+        Self { current: 0, queue }
     }
 
     /// borrows the current frame and allows us to fuck w it
@@ -26,55 +26,51 @@ impl Renderer {
     /// moves to the next frame in the queue
     pub fn advance(&mut self) {
         // will prolly make this throw an error soon
-        if self.current + 1 < self.queue.len() {return}
+        if self.current + 1 >= self.queue.len() {
+            return;
+        }
         self.current += 1;
     }
 
-    /// shitty chunking approach that we have to use frn cuz i'm too lazy to make this on u32. we'll deal w this later
-    pub fn clear(&mut self, fb: &mut Frame, color: Color) {
-        let packed = [color.r, color.g, color.b, color.a];
-        
-        for chunk in fb.as_bytes_mut().chunks_exact_mut(4) {
-            chunk.copy_from_slice(&packed);
+    // every drawing method takes a `&mut Frame`, a `FrameViewMut`, or a `&mut FrameViewMut`.
+    // coordinates are relative to the view, and nothing is ever drawn outside it
+
+    /// fill the whole frame (or view). one packed word per pixel so each row is a plain slice fill
+    pub fn clear<'a>(&mut self, fb: impl Into<FrameViewMut<'a>>, color: Color) {
+        let packed = color.pack_rgba();
+        for row in fb.into().rows_mut() {
+            row.fill(packed);
         }
     }
 
     /// write one pixel (DONT DO THIS UNLESS WE'RE REALLY PRECISE. spans/rects are way better)
-    pub fn set_pixel(&mut self, fb: &mut Frame, x: u16, y: u16, color: Color) {
-        if x >= fb.width() || y >= fb.height() {
-            return;
+    pub fn set_pixel<'a>(&mut self, fb: impl Into<FrameViewMut<'a>>, x: u32, y: u32, color: Color) {
+        // the row is width words long, so x is the only bounds check left
+        if let Some(px) = fb.into().row_mut(y).and_then(|row| row.get_mut(x as usize)) {
+            *px = color.pack_rgba();
         }
-
-        // cast to usize is only for indexing. vecs work off usize is y
-        let x = x as usize;
-        let y = y as usize;
-        let w = fb.width() as usize;
-
-        // offset -> 4 u8s, so y * width + x coord will get you u32 pixel #. 
-        // therefore * 4 will get you u8 red # (then you get green blue and alpha immediately after)
-        let offset = (y * w + x) * 4;
-        let data = fb.as_bytes_mut();
-
-        data[offset] = color.r;
-        data[offset + 1] = color.g;
-        data[offset + 2] = color.b;
-        data[offset + 3] = color.a;
     }
 
     /// plot the span of one row from x0 to x1
-    pub fn hspan(&mut self, fb: &mut Frame, y: u16, x0: u16, x1: u16, color: Color) {
+    pub fn hspan<'a>(
+        &mut self,
+        fb: impl Into<FrameViewMut<'a>>,
+        y: u32,
+        x0: u32,
+        x1: u32,
+        color: Color,
+    ) {
+        let mut fb = fb.into();
         // converting to usize again cuz vectors use it
         let w = fb.width() as usize;
-        let h = fb.height() as usize;
-        let y = y as usize;
-        if y >= h {
+        let Some(row) = fb.row_mut(y) else {
             return;
-        }
+        };
 
         // get start and end (exclusive btw)
         let start = x0.min(x1) as usize;
-        let end   = x0.max(x1) as usize;
-        
+        let end = x0.max(x1) as usize;
+
         // bounds check (we're gonna eventually just do a precheck before rendering and flag anything that's out of bounds)
         // cuz doing this for every hspan is SLOW
         let width = end - start;
@@ -82,22 +78,22 @@ impl Renderer {
             return;
         }
 
-        // offset to first pixel in this row, slice the entire thing
-        let start = (y * w + start) * 4;
-        let len = width * 4;
-        let row_slice = &mut fb.as_bytes_mut()[start .. start + len];
-
-        // pack 4 bytes at a time
-        for chunk in row_slice.chunks_exact_mut(4) {
-            chunk.copy_from_slice(&[color.r, color.g, color.b, color.a]);
-        }
+        // pack once, then write whole words
+        row[start..end].fill(color.pack_rgba());
     }
 
-    /// solid rectangle fill. legit just hspan for row in rows
-    pub fn rect(&mut self, fb: &mut Frame, x: u16, y: u16, width: u16, height: u16, color: Color) {
-        for _ in y..height {
-            self.hspan(fb, y, x, width, color);
-        }
+    /// solid rectangle fill. legit just clear on a view of the rect
+    pub fn rect<'a>(
+        &mut self,
+        fb: impl Into<FrameViewMut<'a>>,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        color: Color,
+    ) {
+        // the view clips to the frame, so rects hanging off an edge still draw their visible part
+        self.clear(fb.into().view_mut(x, y, width, height), color);
     }
 
     /// will be used for the draw queue
@@ -111,11 +107,8 @@ impl Renderer {
     }
 }
 
-
-
 /* =============================================================================
-1) FAST PACKING
-   - pack helper inside of the renderer: `fn pack_rgba(c: Color) -> [u8; 4]`.
+1) FAST PACKING (done: `Color::pack_rgba` -> u32 word)
    - only one time per draw call
 
 2) KERNELS
@@ -125,12 +118,16 @@ impl Renderer {
    - blit_rgba(): per-row `copy_from_slice`
 
 3) (TO SAVE OVERHEAD FROM SLICING) SWITCH TO `Vec<u32>` PIXELS (and add RGBA8888)
-   - why? renderer kernels woukd write `u32` words (which r faster than 4×u8 copies).
-   - switch frame and add conversion in color obviously. 
+   - done. frames store packed words, kernels fill `u32`s, `as_slice` is still a byte view.
+   - `cargo bench --bench clear` compares against the old 4×u8 chunk copies. release builds
+     are memory bound either way, so it's about a wash (~1.02x on a 1080p clear). the win is
+     simpler kernels, not speed.
 
 4) PREMULTIPLIED ALPHA + SRCOVER KERNEL (BLENDING)
-   - store/pack as a premultiplied RGBA.
-   - add blitting: `blit_over()` with integer branchless blend:
-       out = src + dst * (1 - src.a)
-   - split fast paths: a==0 (skip), a==255 (copy), else we'll do a blend loop
-============================================================================= */
\ No newline at end of file
+   - kernel done: `PremulColor::over` in src/color/premul.rs.
+       out = src + dst * (1 - src.a), integer only, exact rounding on the /255
+   - fast paths are in the kernel: a==0 returns dst, a==255 returns src.
+   - frames still store straight alpha. callers convert with `PremulColor::from` and
+     `into_color` around the blend, so nothing else had to change.
+   - still todo: a `blit_over()` that runs the kernel over whole rows of a frame.
+============================================================================= */
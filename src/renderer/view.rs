@@ -0,0 +1,463 @@
+#![allow(dead_code)]
+
+// borrowed rectangles of a frame, so a panel can be drawn with coordinates relative to
+// its own top left corner instead of doing offset math against the whole frame.
+// a view is the frame's words from its first pixel to its last, plus a stride: row y
+// starts at y * stride and the `width` words after that are the view's. rectangles are
+// always clipped to what they're cut from, so a view can't reach outside its frame
+
+use core::ops::Range;
+
+use crate::{color::model::Color, renderer::frame::Frame};
+
+// clip a rect to a `width` x `height` area. empty results are 0x0 so rows() always
+// yields `height` rows
+#[inline]
+pub(crate) fn clip(
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+    width: u32,
+    height: u32,
+) -> (u32, u32, u32, u32) {
+    let x0 = x.min(width);
+    let y0 = y.min(height);
+    let w = x.saturating_add(w).min(width) - x0;
+    let h = y.saturating_add(h).min(height) - y0;
+    if w == 0 || h == 0 {
+        (x0, y0, 0, 0)
+    } else {
+        (x0, y0, w, h)
+    }
+}
+
+// word range covering a (clipped) rect, from its first pixel to the end of its last row.
+// empty rects can sit past the end of the data, so they get an empty range at the start
+#[inline]
+fn words(x: u32, y: u32, w: u32, h: u32, stride: usize) -> Range<usize> {
+    if h == 0 {
+        return 0..0;
+    }
+    let start = y as usize * stride + x as usize;
+    start..start + (h as usize - 1) * stride + w as usize
+}
+
+/// A read only rectangle of a frame's pixels.
+#[derive(Clone, Copy)]
+pub struct FrameView<'a> {
+    data: &'a [u32],
+    width: u32,
+    height: u32,
+    stride: usize,
+}
+
+/// A writable rectangle of a frame's pixels. Every `Renderer` drawing method takes one,
+/// with coordinates relative to the view. Nothing is ever drawn outside it.
+pub struct FrameViewMut<'a> {
+    data: &'a mut [u32],
+    width: u32,
+    height: u32,
+    stride: usize,
+}
+
+impl<'a> FrameView<'a> {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // words between the starts of two rows. the frame's width, not the view's
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        self.row(y)?
+            .get(x as usize)
+            .map(|&px| Color::unpack_rgba(px))
+    }
+
+    // one row of packed pixels, `width` words long
+    pub fn row(&self, y: u32) -> Option<&'a [u32]> {
+        if y >= self.height {
+            return None;
+        }
+        let start = y as usize * self.stride;
+        Some(&self.data[start..start + self.width as usize])
+    }
+
+    // every row top to bottom, skipping the pixels between rows that aren't in the view
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &'a [u32]> + use<'a> {
+        let width = self.width as usize;
+        self.data
+            .chunks(self.stride.max(1))
+            .map(move |row| &row[..width])
+    }
+
+    // a smaller view inside this one. (x, y) is relative to this view and the rect is clipped to it
+    pub fn view(&self, x: u32, y: u32, width: u32, height: u32) -> FrameView<'a> {
+        let (x, y, width, height) = clip(x, y, width, height, self.width, self.height);
+        FrameView {
+            data: &self.data[words(x, y, width, height, self.stride)],
+            width,
+            height,
+            stride: self.stride,
+        }
+    }
+}
+
+impl<'a> FrameViewMut<'a> {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        self.as_view().get_pixel(x, y)
+    }
+
+    pub fn row(&self, y: u32) -> Option<&[u32]> {
+        self.as_view().row(y)
+    }
+
+    pub fn row_mut(&mut self, y: u32) -> Option<&mut [u32]> {
+        if y >= self.height {
+            return None;
+        }
+        let start = y as usize * self.stride;
+        Some(&mut self.data[start..start + self.width as usize])
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[u32]> {
+        self.as_view().rows()
+    }
+
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [u32]> {
+        let width = self.width as usize;
+        self.data
+            .chunks_mut(self.stride.max(1))
+            .map(move |row| &mut row[..width])
+    }
+
+    // read only copy of this view, e.g. to read pixels back while drawing
+    pub fn as_view(&self) -> FrameView<'_> {
+        FrameView {
+            data: self.data,
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+        }
+    }
+
+    pub fn view(&self, x: u32, y: u32, width: u32, height: u32) -> FrameView<'_> {
+        self.as_view().view(x, y, width, height)
+    }
+
+    // a smaller writable view inside this one. (x, y) is relative to this view and the
+    // rect is clipped to it. this view is borrowed until the sub view is dropped
+    pub fn view_mut(&mut self, x: u32, y: u32, width: u32, height: u32) -> FrameViewMut<'_> {
+        let (x, y, width, height) = clip(x, y, width, height, self.width, self.height);
+        FrameViewMut {
+            data: &mut self.data[words(x, y, width, height, self.stride)],
+            width,
+            height,
+            stride: self.stride,
+        }
+    }
+
+    // splits into the rows above `y` and the rows from `y` down, so two panels stacked on
+    // top of each other can be drawn at the same time. `y` is clamped to the height
+    pub fn split_rows_mut(self, y: u32) -> (FrameViewMut<'a>, FrameViewMut<'a>) {
+        let y = y.min(self.height);
+        let (top_h, bottom_h) = (y, self.height - y);
+        let width = |h: u32| if h == 0 { 0 } else { self.width };
+        let stride = self.stride;
+
+        // the top half ends at its last row's last pixel, not at the bottom half's start
+        let (top, bottom) = self
+            .data
+            .split_at_mut((y as usize * stride).min(self.data.len()));
+        let top = &mut top[words(0, 0, width(top_h), top_h, stride)];
+        (
+            FrameViewMut {
+                data: top,
+                width: width(top_h),
+                height: top_h,
+                stride,
+            },
+            FrameViewMut {
+                data: bottom,
+                width: width(bottom_h),
+                height: bottom_h,
+                stride,
+            },
+        )
+    }
+}
+
+// so renderer methods take frames, views, and borrowed views interchangeably
+impl<'a> From<&'a Frame> for FrameView<'a> {
+    fn from(frame: &'a Frame) -> Self {
+        frame.as_view()
+    }
+}
+
+impl<'a> From<&'a mut Frame> for FrameViewMut<'a> {
+    fn from(frame: &'a mut Frame) -> Self {
+        frame.as_view_mut()
+    }
+}
+
+impl<'a> From<&'a mut FrameViewMut<'_>> for FrameViewMut<'a> {
+    fn from(view: &'a mut FrameViewMut<'_>) -> Self {
+        FrameViewMut {
+            data: view.data,
+            width: view.width,
+            height: view.height,
+            stride: view.stride,
+        }
+    }
+}
+
+impl Frame {
+    // the whole frame as a view
+    pub fn as_view(&self) -> FrameView<'_> {
+        FrameView {
+            data: self.as_words(),
+            width: self.width(),
+            height: self.height(),
+            stride: self.width() as usize,
+        }
+    }
+
+    pub fn as_view_mut(&mut self) -> FrameViewMut<'_> {
+        let (width, height) = (self.width(), self.height());
+        FrameViewMut {
+            data: self.as_words_mut(),
+            width,
+            height,
+            stride: width as usize,
+        }
+    }
+
+    // a rectangle of the frame, clipped to it. (x, y) is the rect's top left corner
+    pub fn view(&self, x: u32, y: u32, width: u32, height: u32) -> FrameView<'_> {
+        self.as_view().view(x, y, width, height)
+    }
+
+    pub fn view_mut(&mut self, x: u32, y: u32, width: u32, height: u32) -> FrameViewMut<'_> {
+        let (x, y, width, height) = clip(x, y, width, height, self.width(), self.height());
+        let stride = self.width() as usize;
+        FrameViewMut {
+            data: &mut self.as_words_mut()[words(x, y, width, height, stride)],
+            width,
+            height,
+            stride,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render::Renderer;
+
+    const W: u32 = 10;
+    const H: u32 = 8;
+
+    /// The word every pixel of `numbered` starts with, so views can be checked by position.
+    fn word(x: u32, y: u32) -> u32 {
+        y << 16 | x
+    }
+
+    fn numbered() -> Frame {
+        let mut frame = Frame::new(W, H);
+        for (i, px) in frame.as_words_mut().iter_mut().enumerate() {
+            *px = word(i as u32 % W, i as u32 / W);
+        }
+        frame
+    }
+
+    /// Assert the view covers exactly the frame rect at (x, y) with the given size.
+    fn assert_covers(view: FrameView, x: u32, y: u32, w: u32, h: u32) {
+        assert_eq!((view.width(), view.height()), (w, h));
+        assert_eq!(view.rows().len(), h as usize);
+        for (dy, row) in view.rows().enumerate() {
+            let expected: Vec<u32> = (x..x + w).map(|x| word(x, y + dy as u32)).collect();
+            assert_eq!(row, expected);
+            assert_eq!(view.row(dy as u32), Some(row));
+        }
+        assert_eq!(view.row(h), None);
+    }
+
+    /// Assert only the frame rect at (x, y) with the given size was painted `color`.
+    fn assert_painted(frame: &Frame, x: u32, y: u32, w: u32, h: u32, color: Color) {
+        for py in 0..H {
+            for px in 0..W {
+                let inside = (x..x + w).contains(&px) && (y..y + h).contains(&py);
+                let expected = if inside {
+                    color.pack_rgba()
+                } else {
+                    word(px, py)
+                };
+                assert_eq!(
+                    frame.as_words()[(py * W + px) as usize],
+                    expected,
+                    "({px}, {py})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn clip_keeps_rects_inside() {
+        assert_eq!(clip(2, 3, 4, 5, W, H), (2, 3, 4, 5));
+        assert_eq!(clip(8, 6, 5, 5, W, H), (8, 6, 2, 2));
+        assert_eq!(clip(u32::MAX, 1, u32::MAX, 1, W, H), (W, 1, 0, 0));
+        assert_eq!(clip(3, 3, 0, 4, W, H), (3, 3, 0, 0));
+        assert_eq!(clip(3, 3, 4, 0, W, H), (3, 3, 0, 0));
+        assert_eq!(clip(20, 20, 3, 3, W, H), (W, H, 0, 0));
+    }
+
+    #[test]
+    fn nested_views_offset_from_their_parent() {
+        let frame = numbered();
+        let outer = frame.view(2, 1, 6, 5);
+        assert_covers(outer, 2, 1, 6, 5);
+        assert_eq!(outer.stride(), W as usize);
+        assert_covers(outer.view(1, 2, 3, 2), 3, 3, 3, 2);
+        // the inner view is clipped to the outer one, not to the frame
+        assert_covers(outer.view(4, 3, 10, 10), 6, 4, 2, 2);
+
+        let mut frame = numbered();
+        let mut outer = frame.view_mut(2, 1, 6, 5);
+        assert_covers(outer.view(1, 2, 3, 2), 3, 3, 3, 2);
+        let mut inner = outer.view_mut(1, 2, 3, 2);
+        assert_covers(inner.as_view(), 3, 3, 3, 2);
+        assert_eq!(inner.get_pixel(2, 1), Some(Color::unpack_rgba(word(5, 4))));
+
+        let red = Color::new(255, 0, 0, 255);
+        inner.row_mut(1).unwrap()[2] = red.pack_rgba();
+        assert_painted(&frame, 5, 4, 1, 1, red);
+    }
+
+    #[test]
+    fn views_hanging_off_every_edge_are_clipped() {
+        let frame = numbered();
+        // right, bottom, and the bottom right corner
+        assert_covers(frame.view(7, 2, 5, 3), 7, 2, 3, 3);
+        assert_covers(frame.view(1, 6, 4, 5), 1, 6, 4, 2);
+        assert_covers(frame.view(8, 5, u32::MAX, u32::MAX), 8, 5, 2, 3);
+        // bigger than the frame on every side
+        assert_covers(frame.view(0, 0, u32::MAX, u32::MAX), 0, 0, W, H);
+        // the last pixel
+        assert_covers(frame.view(W - 1, H - 1, 1, 1), W - 1, H - 1, 1, 1);
+    }
+
+    #[test]
+    fn empty_and_outside_views_draw_nothing() {
+        let mut renderer = Renderer::new(Vec::new());
+        let green = Color::new(0, 255, 0, 255);
+        let mut frame = numbered();
+
+        for (x, y, w, h) in [
+            (3, 3, 0, 4),
+            (3, 3, 4, 0),
+            (W, 0, 3, 3),
+            (0, H, 3, 3),
+            (20, 20, 3, 3),
+            (u32::MAX, u32::MAX, u32::MAX, u32::MAX),
+        ] {
+            let view = frame.view(x, y, w, h);
+            assert_eq!((view.width(), view.height()), (0, 0));
+            assert_eq!(view.rows().len(), 0);
+            assert_eq!(view.row(0), None);
+            assert_eq!(view.get_pixel(0, 0), None);
+
+            let mut view = frame.view_mut(x, y, w, h);
+            assert_eq!((view.width(), view.height()), (0, 0));
+            assert_eq!(view.rows_mut().len(), 0);
+            renderer.clear(&mut view, green);
+            renderer.set_pixel(&mut view, 0, 0, green);
+            renderer.hspan(&mut view, 0, 0, 1, green);
+            renderer.rect(&mut view, 0, 0, 5, 5, green);
+            assert_eq!(view.view_mut(0, 0, 5, 5).height(), 0);
+        }
+        assert_painted(&frame, 0, 0, 0, 0, green);
+    }
+
+    #[test]
+    fn split_rows_mut_at_every_boundary() {
+        let blue = Color::new(0, 0, 255, 255);
+        for split in [0, 1, 3, 4, 5] {
+            let mut frame = numbered();
+            let (mut top, mut bottom) = frame.view_mut(2, 1, 6, 5).split_rows_mut(split);
+            let top_h = split.min(5);
+            let top_w = if top_h == 0 { 0 } else { 6 };
+            let bottom_w = if top_h == 5 { 0 } else { 6 };
+            assert_eq!((top.width(), top.height()), (top_w, top_h));
+            assert_eq!((bottom.width(), bottom.height()), (bottom_w, 5 - top_h));
+            assert_eq!(top.rows_mut().len(), top_h as usize);
+            assert_eq!(bottom.rows_mut().len(), 5 - top_h as usize);
+            assert_covers(top.as_view(), 2, 1, top_w, top_h);
+            if bottom_w != 0 {
+                assert_covers(bottom.as_view(), 2, 1 + top_h, 6, 5 - top_h);
+            }
+
+            // painting the bottom half touches only its own rows
+            for row in bottom.rows_mut() {
+                row.fill(blue.pack_rgba());
+            }
+            assert_painted(&frame, 2, 1 + top_h, bottom_w, 5 - top_h, blue);
+        }
+
+        // splits past the end are clamped to the height
+        let mut frame = numbered();
+        let (top, bottom) = frame.as_view_mut().split_rows_mut(100);
+        assert_eq!((top.width(), top.height()), (W, H));
+        assert_eq!((bottom.width(), bottom.height()), (0, 0));
+    }
+
+    #[test]
+    fn drawing_through_a_view_stays_inside_it() {
+        let mut renderer = Renderer::new(Vec::new());
+        let red = Color::new(255, 0, 0, 255);
+
+        // a rect hanging off the view's bottom right only fills the overlap
+        let mut frame = numbered();
+        let mut view = frame.view_mut(2, 2, 4, 3);
+        renderer.rect(&mut view, 2, 1, 10, 10, red);
+        assert_painted(&frame, 4, 3, 2, 2, red);
+
+        // a full width span
+        let mut frame = numbered();
+        let mut view = frame.view_mut(2, 2, 4, 3);
+        renderer.hspan(&mut view, 2, 4, 0, red);
+        assert_painted(&frame, 2, 4, 4, 1, red);
+
+        // spans past the view's right edge or below it are dropped
+        let mut frame = numbered();
+        let mut view = frame.view_mut(2, 2, 4, 3);
+        renderer.hspan(&mut view, 0, 1, 5, red);
+        renderer.hspan(&mut view, 3, 0, 4, red);
+        renderer.set_pixel(&mut view, 4, 0, red);
+        renderer.set_pixel(&mut view, 0, 3, red);
+        assert_painted(&frame, 0, 0, 0, 0, red);
+
+        // clearing the view fills exactly its rect
+        let mut frame = numbered();
+        renderer.clear(frame.view_mut(7, 6, 5, 5), red);
+        assert_painted(&frame, 7, 6, 3, 2, red);
+    }
+}